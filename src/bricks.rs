@@ -0,0 +1,133 @@
+use std::{
+    collections::{hash_map::DefaultHasher, HashMap},
+    hash::{Hash, Hasher},
+};
+
+use rayon::prelude::{IntoParallelIterator, ParallelIterator};
+
+use crate::TileableCloudNoise;
+
+/// A volume split in cubic bricks for sparse virtual textures, built by [`Self::new`].
+///
+/// Every brick stores its texels surrounded by `border` texels copied from its neighbors,
+/// wrapping around the borders of the volume as it tiles, so that bricks filter seamlessly when
+/// sampled on their own. Identical bricks, such as constant ones, are stored once.
+#[derive(Clone, Debug, PartialEq)]
+pub struct BrickedVolume {
+    /// Texels along each axis of a brick, without its borders.
+    pub brick_size: u32,
+    /// Texels added on every side of a brick.
+    pub border: u32,
+    /// Number of bricks along each axis of the volume.
+    pub bricks_per_axis: u32,
+    pub num_channels: u32,
+    pub bytes_per_channel: u32,
+    pub channel_names: Vec<String>,
+    /// Stored bricks one after the other, each laid out as [`TileableCloudNoise::data`] with
+    /// [`Self::padded_size`] texels along each axis.
+    pub data: Vec<u8>,
+    /// Index of the stored brick at every position of the volume, indexed by
+    /// `(z * bricks_per_axis + y) * bricks_per_axis + x` in bricks.
+    pub index: Vec<u32>,
+}
+
+impl BrickedVolume {
+    /// Splits `volume` in bricks of `brick_size`³ texels with `border` texels on every side, or
+    /// returns `None` if `brick_size` is 0 or does not divide the resolution.
+    pub fn new(volume: &TileableCloudNoise, brick_size: u32, border: u32) -> Option<Self> {
+        if brick_size == 0 || volume.resolution % brick_size != 0 {
+            return None;
+        }
+
+        let res = volume.resolution as i64;
+        let bricks_per_axis = volume.resolution / brick_size;
+        let padded_size = brick_size + 2 * border;
+        let texel_len = (volume.num_channels * volume.bytes_per_channel) as usize;
+
+        let bricks = (0..bricks_per_axis.pow(3))
+            .into_par_iter()
+            .map(|i| {
+                let position = [
+                    i % bricks_per_axis,
+                    (i / bricks_per_axis) % bricks_per_axis,
+                    i / (bricks_per_axis * bricks_per_axis),
+                ];
+                let origin = position.map(|p| (p * brick_size) as i64 - border as i64);
+                // Border texels wrap around the volume
+                let wrap = |axis: usize, i: i64| (origin[axis] + i).rem_euclid(res) as usize;
+
+                let mut brick = Vec::with_capacity(padded_size.pow(3) as usize * texel_len);
+                for z in 0..padded_size as i64 {
+                    for y in 0..padded_size as i64 {
+                        for x in 0..padded_size as i64 {
+                            let [x, y, z] = [wrap(0, x), wrap(1, y), wrap(2, z)];
+                            let texel = (z * res as usize + y) * res as usize + x;
+                            brick.extend_from_slice(
+                                &volume.data[texel * texel_len..(texel + 1) * texel_len],
+                            );
+                        }
+                    }
+                }
+                brick
+            })
+            .collect::<Vec<_>>();
+
+        // Store every distinct brick once
+        let mut data = Vec::new();
+        let mut index = Vec::with_capacity(bricks.len());
+        let mut stored = HashMap::<u64, Vec<u32>>::new();
+        let brick_len = bricks.first().map_or(0, Vec::len);
+        for brick in &bricks {
+            let mut hasher = DefaultHasher::new();
+            brick.hash(&mut hasher);
+            let candidates = stored.entry(hasher.finish()).or_default();
+
+            let existing = candidates.iter().copied().find(|&stored_index| {
+                let start = stored_index as usize * brick_len;
+                data[start..start + brick_len] == brick[..]
+            });
+            let brick_index = existing.unwrap_or_else(|| {
+                let brick_index = (data.len() / brick_len.max(1)) as u32;
+                data.extend_from_slice(brick);
+                candidates.push(brick_index);
+                brick_index
+            });
+            index.push(brick_index);
+        }
+
+        Some(Self {
+            brick_size,
+            border,
+            bricks_per_axis,
+            num_channels: volume.num_channels,
+            bytes_per_channel: volume.bytes_per_channel,
+            channel_names: volume.channel_names.clone(),
+            data,
+            index,
+        })
+    }
+
+    /// Texels along each axis of a stored brick, including its borders.
+    pub fn padded_size(&self) -> u32 {
+        self.brick_size + 2 * self.border
+    }
+
+    /// Number of distinct bricks stored in [`Self::data`].
+    pub fn stored_brick_count(&self) -> u32 {
+        (self.data.len() / self.brick_len().max(1)) as u32
+    }
+
+    /// Data of the brick at `position`, in bricks along each axis.
+    pub fn brick(&self, position: [u32; 3]) -> &[u8] {
+        let [x, y, z] = position;
+        let stored_index =
+            self.index[((z * self.bricks_per_axis + y) * self.bricks_per_axis + x) as usize];
+        let start = stored_index as usize * self.brick_len();
+        &self.data[start..start + self.brick_len()]
+    }
+
+    // Bytes of a stored brick
+    fn brick_len(&self) -> usize {
+        self.padded_size().pow(3) as usize * (self.num_channels * self.bytes_per_channel) as usize
+    }
+}
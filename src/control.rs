@@ -0,0 +1,97 @@
+use std::{
+    error::Error,
+    fmt, io,
+    sync::{
+        atomic::{AtomicBool, AtomicU32, Ordering},
+        Arc,
+    },
+};
+
+/// Progress reporting and cancellation of a bake, see [`GenerationDesc::control`].
+///
+/// Clones share the same cancel flag, so a clone kept by the caller, for example the UI thread of
+/// an editor, can abort a bake running on another thread.
+///
+/// [`GenerationDesc::control`]: crate::GenerationDesc::control
+#[derive(Clone, Default)]
+pub struct GenerationControl {
+    cancelled: Arc<AtomicBool>,
+    progress: Option<Arc<dyn Fn(u32, u32) + Send + Sync>>,
+}
+
+impl GenerationControl {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Calls `progress` with the number of completed slices and the total number of slices
+    /// every time a slice is completed.
+    ///
+    /// Slices are generated in parallel, so `progress` is called from the worker threads, and
+    /// slices complete in any order.
+    pub fn with_progress(mut self, progress: impl Fn(u32, u32) + Send + Sync + 'static) -> Self {
+        self.progress = Some(Arc::new(progress));
+        self
+    }
+
+    /// Stops the bakes using this control, which return [`Cancelled`] as soon as every worker
+    /// thread finishes its current row of texels.
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Relaxed)
+    }
+
+    // Counts the slices completed by one bake out of `total`
+    pub(crate) fn slice_counter(&self, total: u32) -> SliceCounter<'_> {
+        SliceCounter {
+            control: self,
+            completed: AtomicU32::new(0),
+            total,
+        }
+    }
+}
+
+impl fmt::Debug for GenerationControl {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("GenerationControl")
+            .field("cancelled", &self.is_cancelled())
+            .field("progress", &self.progress.is_some())
+            .finish()
+    }
+}
+
+pub(crate) struct SliceCounter<'a> {
+    control: &'a GenerationControl,
+    completed: AtomicU32,
+    total: u32,
+}
+
+impl SliceCounter<'_> {
+    pub(crate) fn complete_slice(&self) {
+        let completed = self.completed.fetch_add(1, Ordering::Relaxed) + 1;
+        if let Some(progress) = &self.control.progress {
+            progress(completed, self.total);
+        }
+    }
+}
+
+/// Error returned by the generators when their [`GenerationControl`] is cancelled.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Cancelled;
+
+impl fmt::Display for Cancelled {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("generation was cancelled")
+    }
+}
+
+impl Error for Cancelled {}
+
+impl From<Cancelled> for io::Error {
+    fn from(cancelled: Cancelled) -> Self {
+        io::Error::other(cancelled)
+    }
+}
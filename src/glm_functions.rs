@@ -1,3 +1,5 @@
+use std::ops::{Add, Div, Mul, Sub};
+
 use glam::{DVec4, Vec2, Vec3, Vec4};
 
 pub(crate) fn lerp(a: f32, b: f32, t: f32) -> f32 {
@@ -34,7 +36,9 @@ fn glm_fade(x: Vec4) -> Vec4 {
 }
 
 // From https://github.com/g-truc/glm/blob/master/glm/gtc/noise.inl
-pub(crate) fn glm_perlin_vec4(p: Vec4, rep: Vec4) -> f32 {
+//
+// `seed` is added to the first permutation, selecting one of 289 gradient sets.
+pub(crate) fn glm_perlin_vec4(p: Vec4, rep: Vec4, seed: f32) -> f32 {
     let pi0 = glm_mod_4(p.floor(), rep); // Integer part modulo rep
     let pi1 = glm_mod_4(pi0 + Vec4::ONE, rep); // Integer part + 1 mod rep
     let pf0 = p.fract(); // Fractional part for interpolation
@@ -46,7 +50,7 @@ pub(crate) fn glm_perlin_vec4(p: Vec4, rep: Vec4) -> f32 {
     let iw0 = Vec4::splat(pi0.w);
     let iw1 = Vec4::splat(pi1.w);
 
-    let ixy = glm_permute(glm_permute(ix) + iy);
+    let ixy = glm_permute(glm_permute(ix + Vec4::splat(seed)) + iy);
     let ixy0 = glm_permute(ixy + iz0);
     let ixy1 = glm_permute(ixy + iz1);
     let ixy00 = glm_permute(ixy0 + iw0);
@@ -188,3 +192,214 @@ pub(crate) fn glm_perlin_vec4(p: Vec4, rep: Vec4) -> f32 {
 
     2.2 * n_xyzw
 }
+
+/// The same coordinate of several points, one per SIMD lane.
+pub(crate) trait Lanes:
+    Copy + Add<Output = Self> + Sub<Output = Self> + Mul<Output = Self> + Div<Output = Self>
+{
+    const COUNT: usize;
+
+    fn splat(value: f32) -> Self;
+    fn from_slice(values: &[f32]) -> Self;
+    fn write_to_slice(self, values: &mut [f32]);
+    fn floor(self) -> Self;
+    fn abs(self) -> Self;
+    fn min(self, rhs: Self) -> Self;
+    fn max(self, rhs: Self) -> Self;
+    // `x < edge ? 0 : 1`
+    fn step(edge: Self, x: Self) -> Self;
+    fn taylor_inv_sqrt(self) -> Self;
+}
+
+impl Lanes for Vec4 {
+    const COUNT: usize = 4;
+
+    fn splat(value: f32) -> Self {
+        Vec4::splat(value)
+    }
+
+    fn from_slice(values: &[f32]) -> Self {
+        Vec4::from_slice(values)
+    }
+
+    fn write_to_slice(self, values: &mut [f32]) {
+        Vec4::write_to_slice(self, values)
+    }
+
+    fn floor(self) -> Self {
+        Vec4::floor(self)
+    }
+
+    fn abs(self) -> Self {
+        Vec4::abs(self)
+    }
+
+    fn min(self, rhs: Self) -> Self {
+        Vec4::min(self, rhs)
+    }
+
+    fn max(self, rhs: Self) -> Self {
+        Vec4::max(self, rhs)
+    }
+
+    fn step(edge: Self, x: Self) -> Self {
+        glm_step(edge, x)
+    }
+
+    fn taylor_inv_sqrt(self) -> Self {
+        taylor_inv_sqrt(self)
+    }
+}
+
+/// Eight lanes as two [`Vec4`]s.
+#[derive(Clone, Copy)]
+pub(crate) struct Vec4x2(Vec4, Vec4);
+
+macro_rules! impl_vec4x2_op {
+    ($trait:ident, $fn:ident) => {
+        impl $trait for Vec4x2 {
+            type Output = Self;
+
+            fn $fn(self, rhs: Self) -> Self {
+                Self(self.0.$fn(rhs.0), self.1.$fn(rhs.1))
+            }
+        }
+    };
+}
+
+impl_vec4x2_op!(Add, add);
+impl_vec4x2_op!(Sub, sub);
+impl_vec4x2_op!(Mul, mul);
+impl_vec4x2_op!(Div, div);
+
+impl Lanes for Vec4x2 {
+    const COUNT: usize = 8;
+
+    fn splat(value: f32) -> Self {
+        Self(Vec4::splat(value), Vec4::splat(value))
+    }
+
+    fn from_slice(values: &[f32]) -> Self {
+        Self(Vec4::from_slice(values), Vec4::from_slice(&values[4..]))
+    }
+
+    fn write_to_slice(self, values: &mut [f32]) {
+        self.0.write_to_slice(values);
+        self.1.write_to_slice(&mut values[4..]);
+    }
+
+    fn floor(self) -> Self {
+        Self(self.0.floor(), self.1.floor())
+    }
+
+    fn abs(self) -> Self {
+        Self(self.0.abs(), self.1.abs())
+    }
+
+    fn min(self, rhs: Self) -> Self {
+        Self(self.0.min(rhs.0), self.1.min(rhs.1))
+    }
+
+    fn max(self, rhs: Self) -> Self {
+        Self(self.0.max(rhs.0), self.1.max(rhs.1))
+    }
+
+    fn step(edge: Self, x: Self) -> Self {
+        Self(glm_step(edge.0, x.0), glm_step(edge.1, x.1))
+    }
+
+    fn taylor_inv_sqrt(self) -> Self {
+        Self(taylor_inv_sqrt(self.0), taylor_inv_sqrt(self.1))
+    }
+}
+
+// `Vec4::dot` in the order of additions glam uses, so every lane matches `glm_perlin_vec4`
+fn dot4<L: Lanes>(a: [L; 4], b: [L; 4]) -> L {
+    if cfg!(target_feature = "sse2") {
+        (a[0] * b[0] + a[2] * b[2]) + (a[1] * b[1] + a[3] * b[3])
+    } else {
+        a[0] * b[0] + a[1] * b[1] + a[2] * b[2] + a[3] * b[3]
+    }
+}
+
+fn lanes_mod<L: Lanes>(x: L, mod_val: L) -> L {
+    x - mod_val * (x / mod_val).floor()
+}
+
+fn lanes_permute<L: Lanes>(x: L) -> L {
+    let x = (x * L::splat(34.0) + L::splat(1.0)) * x;
+    x - (x * L::splat(1.0 / 289.0)).floor() * L::splat(289.0)
+}
+
+fn lanes_fract<L: Lanes>(x: L) -> L {
+    x - x.floor()
+}
+
+// Normalized gradient of the four corners sharing their z and w coordinates, in the order
+// (x0, y0), (x1, y0), (x0, y1), (x1, y1)
+fn lanes_gradients<L: Lanes>(ixy: [L; 4]) -> [[L; 4]; 4] {
+    ixy.map(|ixy| {
+        let gx = ixy / L::splat(7.0);
+        let gy = gx.floor() / L::splat(7.0);
+        let gz = gy.floor() / L::splat(6.0);
+        let mut gx = lanes_fract(gx) - L::splat(0.5);
+        let mut gy = lanes_fract(gy) - L::splat(0.5);
+        let gz = lanes_fract(gz) - L::splat(0.5);
+        let gw = L::splat(0.75) - gx.abs() - gy.abs() - gz.abs();
+        let sw = L::step(gw, L::splat(0.0));
+        gx = gx - sw * (L::step(L::splat(0.0), gx) - L::splat(0.5));
+        gy = gy - sw * (L::step(L::splat(0.0), gy) - L::splat(0.5));
+
+        let g = [gx, gy, gz, gw];
+        let norm = dot4(g, g).taylor_inv_sqrt();
+        g.map(|g| g * norm)
+    })
+}
+
+// `glm_perlin_vec4` evaluated for every lane of `p` at once, with identical results
+pub(crate) fn glm_perlin_lanes<L: Lanes>(p: [L; 4], rep: [L; 4], seed: f32) -> L {
+    let pi0: [L; 4] = std::array::from_fn(|i| lanes_mod(p[i].floor(), rep[i]));
+    let pi1: [L; 4] = std::array::from_fn(|i| lanes_mod(pi0[i] + L::splat(1.0), rep[i]));
+    let pf0 = p.map(lanes_fract);
+    let pf1 = pf0.map(|pf0| pf0 - L::splat(1.0));
+
+    // Corners are indexed by their x, y, z and w bits, with x as the lowest one
+    let ix = |corner: usize| if corner & 1 == 0 { pi0[0] } else { pi1[0] };
+    let iy = |corner: usize| if corner & 2 == 0 { pi0[1] } else { pi1[1] };
+    let ixy: [L; 4] =
+        std::array::from_fn(|xy| lanes_permute(lanes_permute(ix(xy) + L::splat(seed)) + iy(xy)));
+    let ixy_z = [pi0[2], pi1[2]].map(|iz| ixy.map(|ixy| lanes_permute(ixy + iz)));
+    // Indexed by z, then w
+    let gradients = ixy_z.map(|ixy_z| {
+        [pi0[3], pi1[3]].map(|iw| lanes_gradients(ixy_z.map(|ixy| lanes_permute(ixy + iw))))
+    });
+
+    let n = |corner: usize| {
+        let g = gradients[(corner >> 2) & 1][corner >> 3][corner & 3];
+        let pf = std::array::from_fn(|i| {
+            if corner & (1 << i) == 0 {
+                pf0[i]
+            } else {
+                pf1[i]
+            }
+        });
+        dot4(g, pf)
+    };
+
+    let fade =
+        pf0.map(|x| (x * x * x) * (x * (x * L::splat(6.0) - L::splat(15.0)) + L::splat(10.0)));
+    let lerp_lanes = |a: L, b: L, t: L| a + (b - a) * t;
+
+    let n_zw: [L; 4] = std::array::from_fn(|xy| {
+        let n_0w = lerp_lanes(n(xy), n(xy | 8), fade[3]);
+        let n_1w = lerp_lanes(n(xy | 4), n(xy | 12), fade[3]);
+        lerp_lanes(n_0w, n_1w, fade[2])
+    });
+    let n_yzw = [
+        lerp_lanes(n_zw[0], n_zw[2], fade[1]),
+        lerp_lanes(n_zw[1], n_zw[3], fade[1]),
+    ];
+    let n_xyzw = n_yzw[0] * (L::splat(1.0) - fade[0]) + n_yzw[1] * fade[0];
+
+    L::splat(2.2) * n_xyzw
+}
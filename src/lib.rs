@@ -1,19 +1,192 @@
 #[cfg(feature = "images")]
-use std::{fs, path::Path, sync::Arc};
+use std::{fs, path::Path};
 
-use glam::Vec3;
+use glam::{Vec3, Vec3Swizzles};
 use rayon::prelude::{IntoParallelIterator, ParallelIterator};
 
+mod bricks;
+mod control;
 mod glm_functions;
+#[cfg(feature = "exr")]
+mod openexr;
+mod post_process;
+mod presets;
+#[cfg(feature = "images")]
+mod preview;
+mod quantization;
+#[cfg(feature = "serde")]
+mod raw;
+mod recipe;
+mod region;
+#[cfg(feature = "spectrum")]
+mod spectrum;
+mod stats;
+mod stream;
+mod supersampling;
+mod threads;
 mod tileable_3d_noise;
+mod vdb;
+
+pub use bricks::BrickedVolume;
+pub use control::{Cancelled, GenerationControl};
+pub use post_process::ScaleBias;
+pub use presets::{CloudPreset, CloudTextures};
+#[cfg(feature = "images")]
+pub use preview::SliceLayout;
+pub use quantization::Quantization;
+use quantization::Quantizer;
+pub use recipe::{
+    BandLimit, DetailAlpha, DetailChannels, DetailOctaves, NoiseNode, PerlinWorleyMode, Recipe,
+    RecipeChannel, WeightedNode,
+};
+pub use region::Region;
+#[cfg(feature = "spectrum")]
+pub use spectrum::PowerSpectrum;
+pub use stats::ChannelStats;
+pub use stream::StreamFormat;
+pub use supersampling::{ReconstructionFilter, SamplePattern, Supersampling};
+use threads::BakePool;
+pub use threads::Threads;
+pub use tileable_3d_noise::{PerlinBackend, PerlinTable, Tileable3dNoise, WorleyGrid};
+
+/// Storage format of every channel in [`TileableCloudNoise::data`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum TexelFormat {
+    #[default]
+    Unorm8,
+    /// Little-endian `u16`.
+    Unorm16,
+    /// Little-endian `f32`.
+    Float32,
+}
+
+impl TexelFormat {
+    pub fn bytes_per_channel(self) -> u32 {
+        match self {
+            Self::Unorm8 => 1,
+            Self::Unorm16 => 2,
+            Self::Float32 => 4,
+        }
+    }
+
+    pub fn from_bytes_per_channel(bytes_per_channel: u32) -> Option<Self> {
+        match bytes_per_channel {
+            1 => Some(Self::Unorm8),
+            2 => Some(Self::Unorm16),
+            4 => Some(Self::Float32),
+            _ => None,
+        }
+    }
+
+    fn decode(self, bytes: &[u8]) -> f32 {
+        match self {
+            Self::Unorm8 => bytes[0] as f32 / 255.0,
+            Self::Unorm16 => u16::from_le_bytes([bytes[0], bytes[1]]) as f32 / 65535.0,
+            Self::Float32 => f32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]),
+        }
+    }
+
+    // `threshold` is added to integer values before truncating them.
+    fn encode(self, value: f32, threshold: f32, data: &mut Vec<u8>) {
+        match self {
+            Self::Unorm8 => data.push((value * 255.0 + threshold) as u8),
+            Self::Unorm16 => {
+                data.extend_from_slice(&((value * 65535.0 + threshold) as u16).to_le_bytes())
+            }
+            Self::Float32 => data.extend_from_slice(&value.to_le_bytes()),
+        }
+    }
+}
+
+// Adapts a per-texel function to the rows evaluated by the generators. Their coordinates are
+// ordered slice, row, column, as in the original implementation, and swapped to the axes of
+// `TileableCloudNoise::sample` for `texel`.
+fn rows_from_fn<const N: usize>(
+    texel: impl Fn(Vec3) -> [f32; N] + Sync,
+) -> impl Fn(&[Vec3], &mut [f32]) + Sync {
+    move |coords, values| {
+        for (&coords, values) in coords.iter().zip(values.chunks_mut(N.max(1))) {
+            values.copy_from_slice(&texel(coords.zyx()));
+        }
+    }
+}
+
+/// Settings shared by all texture generators.
+#[derive(Clone, Debug)]
+pub struct GenerationDesc {
+    /// Number of texels along each axis of the cubic volume.
+    pub resolution: u32,
+    /// Selects a different variation of the noise; seed 0 reproduces the original textures.
+    pub seed: u32,
+    pub format: TexelFormat,
+    /// Rounding of the integer formats.
+    pub quantization: Quantization,
+    pub supersampling: Supersampling,
+    /// Removal of the octaves the resolution cannot represent from recipes, see
+    /// [`Recipe::band_limited`]. Functions passed to [`TileableCloudNoise::from_fn`] are not
+    /// affected.
+    pub band_limit: BandLimit,
+    /// Implementation of the Perlin noise of recipes.
+    pub perlin_backend: PerlinBackend,
+    /// Time at which the noise of recipes is evaluated in 4D, looping every 1.0, to bake frames
+    /// of an animation: frame `i` of `n` at time `i / n`. The noise is 3D when `None`, as in the
+    /// reference textures.
+    pub time: Option<f32>,
+    /// Progress reporting and cancellation.
+    pub control: GenerationControl,
+    /// Thread pool running the bake, to keep it from competing with the other users of rayon's
+    /// global pool.
+    pub threads: Threads,
+}
 
-pub use tileable_3d_noise::Tileable3dNoise;
+impl GenerationDesc {
+    pub fn new(resolution: u32) -> Self {
+        Self {
+            resolution,
+            seed: 0,
+            format: TexelFormat::Unorm8,
+            quantization: Quantization::Truncate,
+            supersampling: Supersampling {
+                samples_per_axis: 1,
+                pattern: SamplePattern::Stratified,
+                filter: ReconstructionFilter::Box,
+            },
+            band_limit: BandLimit::Off,
+            perlin_backend: PerlinBackend::Glm,
+            time: None,
+            control: GenerationControl::new(),
+            threads: Threads::Global,
+        }
+    }
+}
 
 pub struct TileableCloudNoise {
     pub data: Vec<u8>,
     pub resolution: u32,
     pub num_channels: u32,
     pub bytes_per_channel: u32,
+    pub channel_names: Vec<String>,
+}
+
+impl TileableCloudNoise {
+    /// Returns the value of `channel` at texel `(x, y, z)`, normalized to `0..=1` for integer
+    /// formats.
+    ///
+    /// `x` is the innermost axis of [`Self::data`] and `z` selects the slice.
+    pub fn sample(&self, x: u32, y: u32, z: u32, channel: u32) -> f32 {
+        let texel = ((z * self.resolution + y) * self.resolution + x) as usize;
+        let offset = (texel * self.num_channels as usize + channel as usize)
+            * self.bytes_per_channel as usize;
+
+        self.format()
+            .decode(&self.data[offset..offset + self.bytes_per_channel as usize])
+    }
+
+    /// Format of the channels, derived from [`Self::bytes_per_channel`].
+    pub fn format(&self) -> TexelFormat {
+        TexelFormat::from_bytes_per_channel(self.bytes_per_channel)
+            .unwrap_or_else(|| panic!("unsupported bytes per channel: {}", self.bytes_per_channel))
+    }
 }
 
 #[cfg(feature = "images")]
@@ -40,120 +213,219 @@ impl TileableCloudNoise {
         new_min + (((og_value - og_min) / (og_max - og_min)) * (new_max - new_min))
     }
 
-    // RGBA8 Unorm
-    //
-    // R: PerlinWorley noise
-    // G: Worley0
-    // B: Worley1
-    // A: Worley2
-    pub fn cloud_shape_and_erosion_texture() -> Self {
-        // As SebH mentions in the reference material, frequency values should be reduced if using a smaller resolution.
-        let frequence_mul = [2.0f32, 8.0f32, 14.0f32, 20.0f32, 26.0f32, 32.0f32]; // special weight for perlin-worley
+    // Evaluates `texels` for every row of texels of the volume in parallel, one slice per task,
+    // averages the samples of `desc.supersampling` and encodes the values it writes for every
+    // texel and channel in `desc.format` and `desc.quantization`.
+    fn generate(
+        desc: &GenerationDesc,
+        pool: &BakePool,
+        channel_names: Vec<String>,
+        texels: impl Fn(&[Vec3], &mut [f32]) + Sync,
+    ) -> Result<Self, Cancelled> {
+        let num_channels = channel_names.len() as u32;
+        let data = Self::generate_region(
+            desc,
+            pool,
+            &Region::full(desc.resolution),
+            num_channels,
+            texels,
+        )?;
+
+        Ok(Self {
+            data,
+            resolution: desc.resolution,
+            num_channels,
+            bytes_per_channel: desc.format.bytes_per_channel(),
+            channel_names,
+        })
+    }
 
-        // Cloud base shape (will be used to generate Perlin-Worley noise in the shader)
-        // Note: all channels could be combined once here to reduce memory bandwith requirements.
+    // Same as `generate` over `region` of the volume, returning its data
+    pub(crate) fn generate_region(
+        desc: &GenerationDesc,
+        pool: &BakePool,
+        region: &Region,
+        num_channels: u32,
+        texels: impl Fn(&[Vec3], &mut [f32]) + Sync,
+    ) -> Result<Vec<u8>, Cancelled> {
+        let [width, height, depth] = region.size();
+        let mut data = Vec::with_capacity(
+            (width * height) as usize
+                * depth as usize
+                * (num_channels * desc.format.bytes_per_channel()) as usize,
+        );
+        Self::generate_slices(
+            desc,
+            pool,
+            region,
+            num_channels,
+            depth,
+            texels,
+            |_, slice| -> Result<(), Cancelled> {
+                data.extend_from_slice(&slice);
+                Ok(())
+            },
+        )?;
+        Ok(data)
+    }
 
-        // !!! If this is reduced, you should also reduce the number of frequencies in the fmb noise  !!!
-        let resolution = 128u32;
-        let num_channels = 4u32;
-        let bytes_per_channel = 1u32;
+    // Same as `generate` over `region` of the volume, passing its slices to `on_slice` in order
+    // with their index in the region instead of keeping them. Batches of `batch_len` slices are
+    // generated in parallel in `pool`, then passed on from the calling thread.
+    //
+    // Checks for cancellation before every row, and reports progress after every slice.
+    pub(crate) fn generate_slices<E: From<Cancelled>>(
+        desc: &GenerationDesc,
+        pool: &BakePool,
+        region: &Region,
+        num_channels: u32,
+        batch_len: u32,
+        texels: impl Fn(&[Vec3], &mut [f32]) + Sync,
+        mut on_slice: impl FnMut(u32, Vec<u8>) -> Result<(), E>,
+    ) -> Result<(), E> {
+        let resolution = desc.resolution;
+        let bytes_per_channel = desc.format.bytes_per_channel();
+        let [width, height, depth] = region.size();
+        // Texels outside the volume wrap around
+        let wrap = |i: i32| i.rem_euclid(resolution as i32) as u32;
 
         let norm_factor = 1.0 / resolution as f32;
+        let quantizer = Quantizer::new(desc.quantization, resolution);
+        let slice_counter = desc.control.slice_counter(depth);
+
+        let generate_slice = |z: u32| {
+            let s = wrap(region.z.start + z as i32);
+            let mut slice: Vec<u8> =
+                Vec::with_capacity((width * height * num_channels * bytes_per_channel) as usize);
+            let row_len = (width * num_channels) as usize;
+            // `chunks` panics on volumes without channels
+            let stride = (num_channels as usize).max(1);
+            let mut values = vec![0.0; row_len];
+            let mut sample_values = vec![0.0; row_len];
+            let mut coords = vec![Vec3::ZERO; width as usize];
+            let mut samples = Vec::new();
+            let mut row_samples = Vec::new();
+
+            for t in region.y.clone().map(wrap) {
+                if desc.control.is_cancelled() {
+                    return None;
+                }
 
-        let cloud_base_shape_texels_unpadded = (0..resolution)
-            .into_par_iter()
-            .flat_map(|s| {
-                let mut slice: Vec<u8> = Vec::with_capacity(
-                    (resolution * resolution * num_channels * bytes_per_channel) as usize,
-                );
-
-                for t in 0..resolution {
-                    for r in 0..resolution {
-                        let coords = Vec3::new(s as f32, t as f32, r as f32) * norm_factor;
-
-                        // Perlin FBM noise
-                        let octave_count = 3u32;
-                        let frequency = 8.0f32;
-
-                        let perlin_noise =
-                            Tileable3dNoise::perlin_noise(coords, frequency, octave_count);
-
-                        let cell_count = 4f32;
-                        let worley_noise_0 = 1.0f32
-                            - Tileable3dNoise::worley_noise(coords, cell_count * frequence_mul[0]);
-                        let worley_noise_1 = 1.0f32
-                            - Tileable3dNoise::worley_noise(coords, cell_count * frequence_mul[1]);
-                        let worley_noise_2 = 1.0f32
-                            - Tileable3dNoise::worley_noise(coords, cell_count * frequence_mul[2]);
-
-                        let worley_fbm = worley_noise_0 * 0.625f32
-                            + worley_noise_1 * 0.25f32
-                            + worley_noise_2 * 0.125f32;
-
-                        // Perlin Worley is based on description in GPU Pro 7: Real Time Volumetric Cloudscapes.
-                        // However, it is not clear the text and the image are matching: images does not seem to match what the result from the description in text would give.
-                        // Also there are a lot of fudge factor in the code, e.g. * 0.2, so it is really up to you to fine the formula you like.
-
-                        // mapping perlin noise in between worley as minimum and 1.0 as maximum (as described in text of p.101 of GPU Pro 7)
-                        let perlin_worley = Self::remap(perlin_noise, 0.0, 1.0, worley_fbm, 1.0);
-
-                        // Matches better what figure 4.7 (not the following up text description p.101). Maps worley between newMin as 0 and perlin as maximum.
-                        // let perlin_worley = Self::remap(worleyFBM, 0.0, 1.0, 0.0, perlinNoise);
-
-                        let cell_count = 4f32;
-                        // let worley_noise_0 =
-                        //     1.0f32 - Tileable3dNoise::worley_noise(coords, cell_count * 1.0f32);
-                        let worley_noise_1 =
-                            1.0f32 - Tileable3dNoise::worley_noise(coords, cell_count * 2.0f32);
-                        let worley_noise_2 =
-                            1.0f32 - Tileable3dNoise::worley_noise(coords, cell_count * 4.0f32);
-                        let worley_noise_3 =
-                            1.0f32 - Tileable3dNoise::worley_noise(coords, cell_count * 8.0f32);
-                        let worley_noise_4 =
-                            1.0f32 - Tileable3dNoise::worley_noise(coords, cell_count * 16.0f32);
-                        // cell_count=2 -> half the frequency of texel, we should not go further (with cellCount = 32 and texture size = 64)
-                        // let worley_noise_5 =
-                        //     1.0f32 - Tileable3dNoise::worley_noise(coords, cell_count * 32.0f32);
-
-                        // Three frequency of Worley FBM noise
-                        let worley_fbm_0 = worley_noise_1 * 0.625f32
-                            + worley_noise_2 * 0.25f32
-                            + worley_noise_3 * 0.125f32;
-                        let worley_fbm_1 = worley_noise_2 * 0.625f32
-                            + worley_noise_3 * 0.25f32
-                            + worley_noise_4 * 0.125f32;
-
-                        // let worley_fbm_2 = worley_noise_3 * 0.625f32
-                        // + worley_noise_4 * 0.25f32
-                        // + worley_noise_5 * 0.125f32;
-
-                        // cell_count=4 -> worleyNoise5 is just noise due to sampling frequency=texel frequency. So only take into account 2 frequencies for FBM
-                        let worley_fbm_2 = worley_noise_3 * 0.75f32 + worley_noise_4 * 0.25f32;
-
-                        slice.push((perlin_worley * 255.0) as u8);
-                        slice.push((worley_fbm_0 * 255.0) as u8);
-                        slice.push((worley_fbm_1 * 255.0) as u8);
-                        slice.push((worley_fbm_2 * 255.0) as u8);
+                row_samples.clear();
+                for r in region.x.clone().map(wrap) {
+                    desc.supersampling.samples([r, t, s], &mut samples);
+                    row_samples.extend_from_slice(&samples);
+                }
+                let sample_count = samples.len();
+
+                values.fill(0.0);
+                for sample in 0..sample_count {
+                    for (x, (r, coords)) in region.x.clone().map(wrap).zip(&mut coords).enumerate()
+                    {
+                        let offset = row_samples[x * sample_count + sample].0;
+                        *coords = (Vec3::new(s as f32, t as f32, r as f32) + offset) * norm_factor;
+                    }
+
+                    texels(&coords, &mut sample_values);
+                    for (x, (values, sample_values)) in values
+                        .chunks_mut(stride)
+                        .zip(sample_values.chunks(stride))
+                        .enumerate()
+                    {
+                        let weight = row_samples[x * sample_count + sample].1;
+                        for (value, sample_value) in values.iter_mut().zip(sample_values) {
+                            *value += sample_value * weight;
+                        }
                     }
                 }
 
-                slice
-            })
-            .collect::<Vec<_>>();
+                for (r, values) in region.x.clone().map(wrap).zip(values.chunks(stride)) {
+                    let threshold = quantizer.threshold(r, t, s);
+                    for &value in values {
+                        desc.format.encode(value, threshold, &mut slice);
+                    }
+                }
+            }
 
-        let output = Self {
-            data: cloud_base_shape_texels_unpadded,
-            resolution,
-            num_channels,
-            bytes_per_channel,
+            slice_counter.complete_slice();
+            Some(slice)
         };
 
+        let batch_len = batch_len.max(1);
+        for batch_start in (0..depth).step_by(batch_len as usize) {
+            let batch_end = (batch_start + batch_len).min(depth);
+            let slices = pool
+                .install(|| {
+                    (batch_start..batch_end)
+                        .into_par_iter()
+                        .map(generate_slice)
+                        .collect::<Option<Vec<_>>>()
+                })
+                .ok_or(Cancelled)?;
+
+            for (z, slice) in (batch_start..).zip(slices) {
+                on_slice(z, slice)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Builds a volume from a per-texel function returning the value of every channel.
+    ///
+    /// `texel` receives the texel coordinates normalized by the resolution, so `0.0..1.0` covers
+    /// the volume exactly once and noise that repeats at 1.0 tiles seamlessly. Its axes are those
+    /// of [`Self::sample`]: `x` runs along the rows of a slice and `z` across slices. It is
+    /// called in parallel, and its results are encoded in `desc.format` in the same layout as the
+    /// built-in textures, in the pool selected by `desc.threads`. Returns [`Cancelled`] if
+    /// `desc.control` is cancelled.
+    ///
+    /// ```
+    /// # use tileable_volume_noise::{GenerationDesc, Tileable3dNoise, TileableCloudNoise};
+    /// let noise = TileableCloudNoise::from_fn(&GenerationDesc::new(8), ["worley"], |uvw| {
+    ///     [1.0 - Tileable3dNoise::worley_noise(uvw, 4.0)]
+    /// })?;
+    /// assert_eq!(noise.data.len(), 8 * 8 * 8);
+    /// # Ok::<(), tileable_volume_noise::Cancelled>(())
+    /// ```
+    pub fn from_fn<const N: usize>(
+        desc: &GenerationDesc,
+        channel_names: [&str; N],
+        texel: impl Fn(Vec3) -> [f32; N] + Sync,
+    ) -> Result<Self, Cancelled> {
+        Self::generate(
+            desc,
+            &desc.threads.pool(),
+            channel_names.map(String::from).to_vec(),
+            rows_from_fn(texel),
+        )
+    }
+
+    // RGBA8 Unorm
+    //
+    // R: PerlinWorley noise
+    // G: Worley0
+    // B: Worley1
+    // A: Worley2
+    pub fn cloud_shape_and_erosion_texture() -> Self {
+        let output = Self::cloud_shape_and_erosion_texture_with(&GenerationDesc::new(128))
+            .expect("a new control is never cancelled");
+
         #[cfg(feature = "images")]
         write_to_png(&output, "cloudShapeAndErosion");
 
         output
     }
 
+    /// Same as [`Self::cloud_shape_and_erosion_texture`], with the resolution, seed and format
+    /// taken from `desc`.
+    ///
+    /// See [`Recipe::cloud_shape_and_erosion`] for how the channels are computed.
+    pub fn cloud_shape_and_erosion_texture_with(desc: &GenerationDesc) -> Result<Self, Cancelled> {
+        // !!! If the resolution is reduced, you should also reduce the number of frequencies in the fmb noise  !!!
+        // (or set `desc.band_limit` to do so automatically)
+        Self::from_recipe(&Recipe::cloud_shape_and_erosion(), desc)
+    }
+
     // RGBA8 Unorm
     //
     // R: Worley FBM 0
@@ -161,75 +433,20 @@ impl TileableCloudNoise {
     // B: Worley FBM 2
     // A: Unused - Set to 255
     pub fn details_texture() -> Self {
-        // Detail texture behing different frequency of Worley noise
-        // Note: all channels could be combined once here to reduce memory bandwith requirements.
-        let num_channels = 4u32;
-        let bytes_per_channel = 1u32;
-        let resolution = 32u32;
-
-        let norm_factor = 1.0 / resolution as f32;
-
-        let cloud_detail_texels_unpadded = (0..resolution)
-            .into_par_iter()
-            .flat_map(|s| {
-                let mut slice: Vec<u8> = Vec::with_capacity(
-                    (resolution * resolution * num_channels * bytes_per_channel) as usize,
-                );
-
-                for t in 0..resolution {
-                    for r in 0..resolution {
-                        let coords = Vec3::new(s as f32, t as f32, r as f32) * norm_factor;
-
-                        // 3 octaves
-                        let cell_count = 2f32;
-                        let worley_noise_0 =
-                            1.0f32 - Tileable3dNoise::worley_noise(coords, cell_count * 1.0f32);
-                        let worley_noise_1 =
-                            1.0f32 - Tileable3dNoise::worley_noise(coords, cell_count * 2.0f32);
-                        let worley_noise_2 =
-                            1.0f32 - Tileable3dNoise::worley_noise(coords, cell_count * 4.0f32);
-                        let worley_noise_3 =
-                            1.0f32 - Tileable3dNoise::worley_noise(coords, cell_count * 8.0f32);
-
-                        let worley_fbm_0 = worley_noise_0 * 0.625f32
-                            + worley_noise_1 * 0.25f32
-                            + worley_noise_2 * 0.125f32;
-                        let worley_fbm_1 = worley_noise_1 * 0.625f32
-                            + worley_noise_2 * 0.25f32
-                            + worley_noise_3 * 0.125f32;
-                        // cell_count=4 -> worleyNoise4 is just noise due to sampling frequency=texel frequency. So only take into account 2 frequencies for FBM
-                        let worley_fbm_2 = worley_noise_2 * 0.75f32 + worley_noise_3 * 0.25f32;
-
-                        // 2 octaves - unused
-                        // let worley_noise_0 = 1.0f32 - Tileable3dNoise::worley_noise(coords, 4.0f32);
-                        // let worley_noise_1 = 1.0f32 - Tileable3dNoise::worley_noise(coords, 7.0f32);
-                        // let worley_noise_2 = 1.0f32 - Tileable3dNoise::worley_noise(coords, 10.0f32);
-                        // let worley_noise_3 = 1.0f32 - Tileable3dNoise::worley_noise(coords, 13.0f32);
-                        // let worley_fbm_0 = worley_noise_0 * 0.75f32 + worley_noise_1 * 0.25f32;
-                        // let worley_fbm_1 = worley_noise_1 * 0.75f32 + worley_noise_2 * 0.25f32;
-                        // let worley_fbm_2 = worley_noise_2 * 0.75f32 + worley_noise_3 * 0.25f32;
-
-                        slice.push((worley_fbm_0 * 255.0) as u8);
-                        slice.push((worley_fbm_1 * 255.0) as u8);
-                        slice.push((worley_fbm_2 * 255.0) as u8);
-                        slice.push(255u8);
-                    }
-                }
-
-                slice
-            })
-            .collect::<Vec<_>>();
-
-        let output = Self {
-            data: cloud_detail_texels_unpadded,
-            resolution,
-            num_channels,
-            bytes_per_channel,
-        };
+        let output = Self::details_texture_with(&GenerationDesc::new(32))
+            .expect("a new control is never cancelled");
 
         #[cfg(feature = "images")]
         write_to_png(&output, "cloudDetails");
 
         output
     }
+
+    /// Same as [`Self::details_texture`], with the resolution, seed and format taken from
+    /// `desc`.
+    ///
+    /// See [`Recipe::details`] for how the channels are computed.
+    pub fn details_texture_with(desc: &GenerationDesc) -> Result<Self, Cancelled> {
+        Self::from_recipe(&Recipe::details(), desc)
+    }
 }
@@ -0,0 +1,494 @@
+use std::{
+    error::Error,
+    fs,
+    io::{self, BufWriter, IsTerminal},
+    path::PathBuf,
+    time::Instant,
+};
+
+use clap::{Parser, ValueEnum};
+use tileable_volume_noise::{
+    BandLimit, CloudPreset, DetailAlpha, DetailChannels, DetailOctaves, GenerationControl,
+    GenerationDesc, PerlinBackend, PerlinWorleyMode, Quantization, Recipe, ReconstructionFilter,
+    SamplePattern, SliceLayout, StreamFormat, Supersampling, TexelFormat, Threads,
+    TileableCloudNoise,
+};
+
+#[derive(Clone, Copy, ValueEnum)]
+enum Texture {
+    /// Perlin-Worley and Worley FBM cloud shape and erosion texture
+    Shape,
+    /// Worley FBM cloud detail texture
+    Details,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum Preset {
+    Sebh,
+    Schneider2015,
+    Nubis2017,
+    Frostbite2016,
+    Mobile,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum PerlinWorley {
+    /// Perlin noise remapped between Worley FBM and 1, as in the text of GPU Pro 7
+    Text,
+    /// Worley FBM remapped between 0 and Perlin noise, as in figure 4.7 of GPU Pro 7
+    Figure4_7,
+    /// Perlin noise dilated by Worley FBM
+    Schneider,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum Octaves {
+    /// Three octaves with cell counts doubling from 2
+    PowerOfTwo,
+    /// Two octaves out of cell counts 4, 7, 10, 13...
+    NonPowerOfTwo,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum Alpha {
+    /// Constant 1
+    Unused,
+    /// No alpha channel
+    Omitted,
+    /// One more Worley FBM channel
+    NextFbm,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum Format {
+    Unorm8,
+    Unorm16,
+    Float32,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum Quantize {
+    Truncate,
+    Round,
+    /// Ordered dithering with a Bayer matrix
+    Ordered,
+    /// Blue noise dithering
+    BlueNoise,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum Pattern {
+    Stratified,
+    Jittered,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum Filter {
+    Box,
+    Gaussian,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum Band {
+    /// Keep every octave
+    Off,
+    /// Drop octaves above the Nyquist frequency
+    Drop,
+    /// Fade octaves out towards the Nyquist frequency
+    Fade,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum Perlin {
+    /// Polynomial hashes, as in the reference textures
+    Glm,
+    /// Permutation table, faster but with a different pattern
+    Table,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum Normalize {
+    /// Maps the minimum and maximum of every channel to 0 and 1
+    MinMax,
+    /// Maps the 1st and 99th percentiles of every channel to 0 and 1
+    Stretch,
+    /// Spreads the values of every channel evenly over 0 to 1; the curves undoing it are written
+    /// to `{output}_curves.json`
+    Equalize,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum Layout {
+    Strip,
+    Atlas,
+}
+
+/// File format of the output, picked from its extension.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Container {
+    Png,
+    Exr,
+    Vdb,
+    Raw,
+    Nrrd,
+}
+
+/// Bakes tileable cloud noise volumes.
+///
+/// The container is picked from the extension of the output path: `.png` writes one grayscale
+/// image per channel, `.exr` a multi-part OpenEXR file, `.vdb` one OpenVDB grid per channel,
+/// `.raw` the texel data with a JSON sidecar and `.nrrd` a NRRD file, streamed to disk a few
+/// slices at a time for volumes too large for memory.
+#[derive(Parser)]
+#[command(version)]
+struct Args {
+    /// Built-in texture to bake
+    #[arg(required_unless_present = "recipe")]
+    texture: Option<Texture>,
+
+    /// JSON recipe to bake instead of a built-in texture
+    #[arg(long, conflicts_with = "texture")]
+    recipe: Option<PathBuf>,
+
+    /// Cloud renderer whose version of the built-in texture is baked
+    #[arg(short, long, value_enum, default_value_t = Preset::Sebh)]
+    preset: Preset,
+
+    /// Combination of Perlin and Worley noise in the first channel of the shape texture
+    #[arg(long, value_enum, conflicts_with_all = ["recipe", "preset"])]
+    perlin_worley: Option<PerlinWorley>,
+
+    /// Worley octaves of the detail texture
+    #[arg(long, value_enum, conflicts_with_all = ["recipe", "preset"])]
+    detail_octaves: Option<Octaves>,
+
+    /// Number of Worley FBM channels of the detail texture
+    #[arg(long, conflicts_with_all = ["recipe", "preset"])]
+    detail_fbm_count: Option<u32>,
+
+    /// Channel following the Worley FBM channels of the detail texture
+    #[arg(long, value_enum, conflicts_with_all = ["recipe", "preset"])]
+    detail_alpha: Option<Alpha>,
+
+    /// Number of texels along each axis; defaults to the resolution of the preset for built-in
+    /// textures and 128 for recipes
+    #[arg(short, long, value_parser = clap::value_parser!(u32).range(1..))]
+    resolution: Option<u32>,
+
+    /// Noise variation, 0 reproduces the reference textures
+    #[arg(short, long, default_value_t = 0)]
+    seed: u32,
+
+    /// Storage format of every channel
+    #[arg(short, long, value_enum, default_value_t = Format::Unorm8)]
+    format: Format,
+
+    /// Rounding of the `unorm8` and `unorm16` formats
+    #[arg(short, long, value_enum, default_value_t = Quantize::Truncate)]
+    quantization: Quantize,
+
+    /// Number of samples per texel along each axis, to anti-alias high frequencies
+    #[arg(long, default_value_t = 1)]
+    samples_per_axis: u32,
+
+    /// Placement of the samples within a texel
+    #[arg(long, value_enum, default_value_t = Pattern::Stratified)]
+    sample_pattern: Pattern,
+
+    /// Weighting of the samples of a texel
+    #[arg(long, value_enum, default_value_t = Filter::Box)]
+    filter: Filter,
+
+    /// Removal of the octaves too fine for the resolution
+    #[arg(short, long, value_enum, default_value_t = Band::Off)]
+    band_limit: Band,
+
+    /// Implementation of Perlin noise
+    #[arg(long, value_enum, default_value_t = Perlin::Glm)]
+    perlin: Perlin,
+
+    /// Time of the frame to bake in an animation looping every 1.0, using 4D noise; frame `i` of
+    /// `n` is at `i / n`
+    #[arg(short, long)]
+    time: Option<f32>,
+
+    /// Number of worker threads; defaults to one per CPU
+    #[arg(short = 'j', long)]
+    threads: Option<usize>,
+
+    /// Post-process improving the use of the range of every channel; the volume is generated
+    /// in `float32` and converted to the output format afterwards
+    #[arg(short, long, value_enum)]
+    normalize: Option<Normalize>,
+
+    /// Print the power spectrum of every channel, warning about aliasing
+    #[arg(long)]
+    spectrum: bool,
+
+    /// Arrangement of the slices in `.png` output
+    #[arg(short, long, value_enum, default_value_t = Layout::Strip)]
+    layout: Layout,
+
+    /// Size of a voxel in world units in `.vdb` output
+    #[arg(long, default_value_t = 1.0)]
+    voxel_size: f64,
+
+    /// Output path
+    #[arg(short, long)]
+    output: PathBuf,
+}
+
+fn print_statistics(noise: &TileableCloudNoise) {
+    for (stats, name) in noise.stats().iter().zip(&noise.channel_names) {
+        println!(
+            "  {name:<16} min {:.4}  max {:.4}  mean {:.4}  std dev {:.4}",
+            stats.min,
+            stats.max,
+            stats.mean,
+            stats.variance.sqrt()
+        );
+        if stats.is_saturated() {
+            println!(
+                "  warning: {name} is saturated at 0 or 1 in {} texels",
+                stats.saturated_count
+            );
+        }
+    }
+}
+
+fn print_spectrum(noise: &TileableCloudNoise) {
+    for (channel, name) in (0..noise.num_channels).zip(&noise.channel_names) {
+        let spectrum = noise.power_spectrum(channel);
+        let power = spectrum
+            .power
+            .iter()
+            .map(|power| format!("{power:.3e}"))
+            .collect::<Vec<_>>()
+            .join(" ");
+        println!("  {name:<16} power by frequency: {power}");
+        println!(
+            "  {:<16} energy above nyquist: {:.2}%",
+            "",
+            spectrum.energy_above_nyquist * 100.0
+        );
+        if spectrum.is_aliased() {
+            println!("  warning: {name} is aliased, lower its frequencies or raise the resolution");
+        }
+    }
+}
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let args = Args::parse();
+
+    // Checked before baking, which can take minutes
+    let output = &args.output;
+    let container = match output.extension().and_then(|extension| extension.to_str()) {
+        Some("png") => Container::Png,
+        Some("exr") => Container::Exr,
+        Some("vdb") => Container::Vdb,
+        Some("raw") => Container::Raw,
+        Some("nrrd") => Container::Nrrd,
+        _ => return Err("output must end in .png, .exr, .vdb, .raw or .nrrd".into()),
+    };
+    let dir = output.parent().unwrap_or(output);
+    let stem = output
+        .file_stem()
+        .and_then(|stem| stem.to_str())
+        .ok_or("output path has no file name")?;
+
+    let preset = match args.preset {
+        Preset::Sebh => CloudPreset::SebH,
+        Preset::Schneider2015 => CloudPreset::Schneider2015,
+        Preset::Nubis2017 => CloudPreset::Nubis2017,
+        Preset::Frostbite2016 => CloudPreset::Frostbite2016,
+        Preset::Mobile => CloudPreset::Mobile,
+    };
+    let perlin_worley = args.perlin_worley.map(|mode| match mode {
+        PerlinWorley::Text => PerlinWorleyMode::TextDescription,
+        PerlinWorley::Figure4_7 => PerlinWorleyMode::Figure4_7,
+        PerlinWorley::Schneider => PerlinWorleyMode::SchneiderDilated,
+    });
+    let detail_channels = (args.detail_octaves.is_some()
+        || args.detail_fbm_count.is_some()
+        || args.detail_alpha.is_some())
+    .then(|| {
+        let default = DetailChannels::default();
+        DetailChannels {
+            octaves: match args.detail_octaves {
+                Some(Octaves::PowerOfTwo) => DetailOctaves::PowerOfTwo,
+                Some(Octaves::NonPowerOfTwo) => DetailOctaves::NonPowerOfTwo,
+                None => default.octaves,
+            },
+            fbm_count: args.detail_fbm_count.unwrap_or(default.fbm_count),
+            alpha: match args.detail_alpha {
+                Some(Alpha::Unused) => DetailAlpha::Unused,
+                Some(Alpha::Omitted) => DetailAlpha::Omitted,
+                Some(Alpha::NextFbm) => DetailAlpha::NextFbm,
+                None => default.alpha,
+            },
+        }
+    });
+    let (recipe, default_desc) = match (&args.recipe, args.texture) {
+        (Some(path), _) => (
+            serde_json::from_slice(&fs::read(path)?)?,
+            GenerationDesc::new(128),
+        ),
+        (None, Some(Texture::Details)) => {
+            if perlin_worley.is_some() {
+                return Err("--perlin-worley only applies to the shape texture".into());
+            }
+            let recipe = match &detail_channels {
+                Some(channels) => Recipe::details_with(channels),
+                None => preset.detail_recipe(),
+            };
+            (recipe, preset.detail_desc())
+        }
+        (None, _) => {
+            if detail_channels.is_some() {
+                return Err("--detail-* options only apply to the details texture".into());
+            }
+            let recipe = match perlin_worley {
+                Some(mode) => Recipe::cloud_shape_and_erosion_with(mode),
+                None => preset.shape_recipe(),
+            };
+            (recipe, preset.shape_desc())
+        }
+    };
+
+    let desc = GenerationDesc {
+        resolution: args.resolution.unwrap_or(default_desc.resolution),
+        seed: args.seed,
+        format: match args.format {
+            Format::Unorm8 => TexelFormat::Unorm8,
+            Format::Unorm16 => TexelFormat::Unorm16,
+            Format::Float32 => TexelFormat::Float32,
+        },
+        quantization: match args.quantization {
+            Quantize::Truncate => Quantization::Truncate,
+            Quantize::Round => Quantization::Round,
+            Quantize::Ordered => Quantization::OrderedDither,
+            Quantize::BlueNoise => Quantization::BlueNoiseDither,
+        },
+        supersampling: Supersampling {
+            samples_per_axis: args.samples_per_axis,
+            pattern: match args.sample_pattern {
+                Pattern::Stratified => SamplePattern::Stratified,
+                Pattern::Jittered => SamplePattern::Jittered,
+            },
+            filter: match args.filter {
+                Filter::Box => ReconstructionFilter::Box,
+                Filter::Gaussian => ReconstructionFilter::Gaussian,
+            },
+        },
+        band_limit: match args.band_limit {
+            Band::Off => BandLimit::Off,
+            Band::Drop => BandLimit::Drop,
+            Band::Fade => BandLimit::Fade,
+        },
+        perlin_backend: match args.perlin {
+            Perlin::Glm => PerlinBackend::Glm,
+            Perlin::Table => PerlinBackend::Table,
+        },
+        time: args.time,
+        control: GenerationControl::new().with_progress(|completed, total| {
+            if io::stderr().is_terminal() {
+                eprint!("\rGenerating slice {completed}/{total}");
+                if completed == total {
+                    eprint!("\r\x1b[K");
+                }
+            }
+        }),
+        threads: match args.threads {
+            Some(count) => Threads::Count(count),
+            None => Threads::Global,
+        },
+    };
+
+    let start = Instant::now();
+    if container == Container::Nrrd {
+        if args.normalize.is_some() || args.spectrum {
+            return Err("--normalize and --spectrum do not apply to streamed .nrrd output".into());
+        }
+        let writer = BufWriter::new(fs::File::create(output)?);
+        TileableCloudNoise::write_recipe_stream(&recipe, &desc, StreamFormat::Nrrd, writer)?;
+        println!(
+            "Streamed {res}x{res}x{res} volume in {:.2?}",
+            start.elapsed(),
+            res = desc.resolution
+        );
+        return Ok(());
+    }
+
+    let noise = match args.normalize {
+        None => TileableCloudNoise::from_recipe(&recipe, &desc)?,
+        Some(normalize) => {
+            let mut noise = TileableCloudNoise::from_recipe(
+                &recipe,
+                &GenerationDesc {
+                    format: TexelFormat::Float32,
+                    ..desc.clone()
+                },
+            )?;
+            let scale_biases = match normalize {
+                Normalize::MinMax => noise.normalize(),
+                Normalize::Stretch => noise.stretch(0.01, 0.99),
+                Normalize::Equalize => {
+                    // The original value at 256 evenly spaced values of every channel
+                    let curves = noise.equalize();
+                    let curves = noise
+                        .channel_names
+                        .iter()
+                        .zip(&curves)
+                        .map(|(name, curve)| {
+                            serde_json::json!({ "channel": name, "curve": curve.to_vec() })
+                        })
+                        .collect::<Vec<_>>();
+                    let path = dir.join(format!("{stem}_curves.json"));
+                    fs::write(&path, serde_json::to_string_pretty(&curves)?)?;
+                    println!(
+                        "  Wrote the curves undoing the equalization to {}",
+                        path.display()
+                    );
+                    Vec::new()
+                }
+            };
+            for (scale_bias, name) in scale_biases.iter().zip(&noise.channel_names) {
+                println!(
+                    "  {name:<16} original = value * {:.6} + {:.6}",
+                    scale_bias.scale, scale_bias.bias
+                );
+            }
+            noise.convert(desc.format, desc.quantization)
+        }
+    };
+    println!(
+        "Generated {res}x{res}x{res} volume in {:.2?}",
+        start.elapsed(),
+        res = noise.resolution
+    );
+    print_statistics(&noise);
+    if args.spectrum {
+        print_spectrum(&noise);
+    }
+
+    match container {
+        Container::Png => {
+            let layout = match args.layout {
+                Layout::Strip => SliceLayout::Strip,
+                Layout::Atlas => SliceLayout::Atlas,
+            };
+            noise.write_channel_pngs(dir, stem, layout)?;
+        }
+        Container::Exr => noise.write_exr(output)?,
+        Container::Vdb => {
+            for (channel, name) in (0..noise.num_channels).zip(&noise.channel_names) {
+                let path = dir.join(format!("{}_{}.vdb", stem, name));
+                noise.write_vdb(path, channel, args.voxel_size)?;
+            }
+        }
+        Container::Raw => noise.write_raw(output)?,
+        Container::Nrrd => unreachable!("NRRD output is streamed"),
+    }
+
+    Ok(())
+}
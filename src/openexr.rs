@@ -0,0 +1,145 @@
+use std::path::Path;
+
+use exr::{meta::attribute::AttributeValue, prelude::*};
+
+use crate::TileableCloudNoise;
+
+// EXR sorts channels by name, so the original order and encoding are stored as layer attributes
+const CHANNEL_NAMES_ATTRIBUTE: &str = "channel_names";
+const BYTES_PER_CHANNEL_ATTRIBUTE: &str = "bytes_per_channel";
+
+impl TileableCloudNoise {
+    /// Writes the volume as a multi-part OpenEXR file with one part per slice, named
+    /// `slice_0000`, `slice_0001`, ...
+    ///
+    /// Every part holds one 32-bit float channel per entry in [`Self::channel_names`]. Integer
+    /// formats are written normalized to `0..=1`, so no precision is lost.
+    pub fn write_exr(&self, path: impl AsRef<Path>) -> Result<()> {
+        let res = self.resolution;
+        let size = (res as usize, res as usize);
+
+        let layers = (0..res)
+            .map(|z| {
+                let channels = (0..self.num_channels)
+                    .zip(&self.channel_names)
+                    .map(|(channel, channel_name)| {
+                        let samples = (0..res)
+                            .flat_map(|y| (0..res).map(move |x| (x, y)))
+                            .map(|(x, y)| self.sample(x, y, z, channel))
+                            .collect();
+                        AnyChannel::new(channel_name.as_str(), FlatSamples::F32(samples))
+                    })
+                    .collect::<SmallVec<_>>();
+
+                let mut attributes = LayerAttributes::named(format!("slice_{:04}", z).as_str());
+                attributes.other.insert(
+                    CHANNEL_NAMES_ATTRIBUTE.into(),
+                    AttributeValue::TextVector(
+                        self.channel_names
+                            .iter()
+                            .map(|name| name.as_str().into())
+                            .collect(),
+                    ),
+                );
+                attributes.other.insert(
+                    BYTES_PER_CHANNEL_ATTRIBUTE.into(),
+                    AttributeValue::I32(self.bytes_per_channel as i32),
+                );
+
+                Layer::new(
+                    size,
+                    attributes,
+                    Encoding::SMALL_LOSSLESS,
+                    AnyChannels::sort(channels),
+                )
+            })
+            .collect::<Vec<_>>();
+
+        Image::from_layers(ImageAttributes::with_size(size), layers)
+            .write()
+            .to_file(path)
+    }
+
+    /// Reads a volume written by [`Self::write_exr`].
+    ///
+    /// Files that lack the channel order and encoding attributes written by this crate are read
+    /// as 32-bit floats with the channels in alphabetical order.
+    pub fn read_exr(path: impl AsRef<Path>) -> Result<Self> {
+        let image = read_all_flat_layers_from_file(path)?;
+        let layers = image.layer_data;
+
+        let first = layers
+            .first()
+            .ok_or_else(|| Error::Invalid("EXR file contains no layers".into()))?;
+        let resolution = first.size.width() as u32;
+        if first.size.height() != first.size.width() || layers.len() != resolution as usize {
+            return Err(Error::Invalid(
+                "EXR file does not contain a cubic volume".into(),
+            ));
+        }
+
+        let channel_names = match first
+            .attributes
+            .other
+            .get(CHANNEL_NAMES_ATTRIBUTE.as_bytes())
+        {
+            Some(AttributeValue::TextVector(names)) => {
+                names.iter().map(|name| name.to_string()).collect()
+            }
+            _ => first
+                .channel_data
+                .list
+                .iter()
+                .map(|channel| channel.name.to_string())
+                .collect::<Vec<_>>(),
+        };
+        let bytes_per_channel = match first
+            .attributes
+            .other
+            .get(BYTES_PER_CHANNEL_ATTRIBUTE.as_bytes())
+        {
+            Some(&AttributeValue::I32(bytes @ (1 | 2 | 4))) => bytes as u32,
+            _ => 4,
+        };
+
+        let texel_count = (resolution * resolution) as usize;
+        let mut data = Vec::with_capacity(
+            texel_count * layers.len() * channel_names.len() * bytes_per_channel as usize,
+        );
+
+        for layer in &layers {
+            let channels = channel_names
+                .iter()
+                .map(|name| {
+                    layer
+                        .channel_data
+                        .list
+                        .iter()
+                        .find(|channel| channel.name == *name.as_str())
+                        .ok_or_else(|| Error::Invalid(format!("missing channel `{}`", name).into()))
+                })
+                .collect::<Result<Vec<_>>>()?;
+
+            for texel in 0..texel_count {
+                for channel in &channels {
+                    let value = channel.sample_data.value_by_flat_index(texel).to_f32();
+                    match bytes_per_channel {
+                        1 => data.push((value.clamp(0.0, 1.0) * 255.0).round() as u8),
+                        2 => data.extend_from_slice(
+                            &((value.clamp(0.0, 1.0) * 65535.0).round() as u16).to_le_bytes(),
+                        ),
+                        _ => data.extend_from_slice(&value.to_le_bytes()),
+                    }
+                }
+            }
+        }
+
+        Ok(Self {
+            data,
+            resolution,
+            num_channels: channel_names.len() as u32,
+            bytes_per_channel,
+            channel_names,
+        })
+    }
+}
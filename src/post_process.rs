@@ -0,0 +1,194 @@
+use rayon::prelude::{IntoParallelIterator, ParallelIterator};
+
+use crate::{quantization::Quantizer, Quantization, TexelFormat, TileableCloudNoise};
+
+/// Linear mapping applied to a channel by [`TileableCloudNoise::normalize`] and
+/// [`TileableCloudNoise::stretch`], to be undone in shaders.
+///
+/// The original value of a texel is `value * scale + bias`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ScaleBias {
+    pub scale: f32,
+    pub bias: f32,
+}
+
+impl ScaleBias {
+    pub const IDENTITY: Self = Self {
+        scale: 1.0,
+        bias: 0.0,
+    };
+
+    // Maps `min..max` to `0..1`, leaving constant channels unchanged
+    fn from_range(min: f32, max: f32) -> Self {
+        if max > min {
+            Self {
+                scale: max - min,
+                bias: min,
+            }
+        } else {
+            Self::IDENTITY
+        }
+    }
+
+    fn apply(self, value: f32) -> f32 {
+        ((value - self.bias) / self.scale).clamp(0.0, 1.0)
+    }
+}
+
+// Value at `percentile` in `0..=1` of the sorted `values`
+fn quantile(values: &[f32], percentile: f32) -> f32 {
+    let index = (percentile.clamp(0.0, 1.0) * (values.len() - 1) as f32).round() as usize;
+    values[index]
+}
+
+// Fraction of `values` below `value`, counting equal values as half below
+fn cdf(values: &[f32], value: f32) -> f32 {
+    let below = values.partition_point(|&v| v < value);
+    let below_or_equal = values.partition_point(|&v| v <= value);
+    (below + below_or_equal) as f32 * 0.5 / values.len() as f32
+}
+
+// Value in `0..=1` at which the non-decreasing `cdf` reaches `probability`
+fn inverse_cdf(cdf: &impl Fn(f32) -> f32, probability: f32) -> f32 {
+    let (mut low, mut high) = (0.0f32, 1.0f32);
+    for _ in 0..32 {
+        let middle = 0.5 * (low + high);
+        if cdf(middle) < probability {
+            low = middle;
+        } else {
+            high = middle;
+        }
+    }
+    0.5 * (low + high)
+}
+
+impl TileableCloudNoise {
+    // Encodes `map(channel, value)` for every value in `format`, one slice per task
+    fn map_values(
+        &self,
+        format: TexelFormat,
+        quantization: Quantization,
+        map: impl Fn(usize, f32) -> f32 + Sync,
+    ) -> Vec<u8> {
+        let res = self.resolution;
+        let num_channels = self.num_channels as usize;
+        let quantizer = Quantizer::new(quantization, res);
+
+        (0..res)
+            .into_par_iter()
+            .flat_map(|z| {
+                let mut slice = Vec::with_capacity(
+                    (res * res) as usize * num_channels * format.bytes_per_channel() as usize,
+                );
+                for y in 0..res {
+                    for x in 0..res {
+                        let threshold = quantizer.threshold(x, y, z);
+                        for channel in 0..num_channels {
+                            let value = map(channel, self.sample(x, y, z, channel as u32));
+                            format.encode(value, threshold, &mut slice);
+                        }
+                    }
+                }
+                slice
+            })
+            .collect()
+    }
+
+    // Values of `channel`, sorted in increasing order
+    fn sorted_values(&self, channel: u32) -> Vec<f32> {
+        let res = self.resolution;
+        let mut values = Vec::with_capacity(res.pow(3) as usize);
+        for z in 0..res {
+            for y in 0..res {
+                for x in 0..res {
+                    values.push(self.sample(x, y, z, channel));
+                }
+            }
+        }
+        values.sort_unstable_by(f32::total_cmp);
+        values
+    }
+
+    /// Returns a copy of the volume stored in `format`, quantized according to `quantization`.
+    ///
+    /// Post-processes are best applied to [`TexelFormat::Float32`] volumes before converting
+    /// them to an integer format, which keeps the precision they gain.
+    pub fn convert(&self, format: TexelFormat, quantization: Quantization) -> Self {
+        Self {
+            data: self.map_values(format, quantization, |_, value| value),
+            resolution: self.resolution,
+            num_channels: self.num_channels,
+            bytes_per_channel: format.bytes_per_channel(),
+            channel_names: self.channel_names.clone(),
+        }
+    }
+
+    /// Linearly maps every channel from its minimum and maximum to `0..=1`.
+    ///
+    /// Returns the mapping of every channel. Integer formats are rounded to nearest.
+    pub fn normalize(&mut self) -> Vec<ScaleBias> {
+        let scale_biases = self
+            .stats()
+            .iter()
+            .map(|stats| ScaleBias::from_range(stats.min, stats.max))
+            .collect::<Vec<_>>();
+
+        self.data = self.map_values(self.format(), Quantization::Round, |channel, value| {
+            scale_biases[channel].apply(value)
+        });
+        scale_biases
+    }
+
+    /// Linearly maps every channel from its values at the `low` and `high` percentiles, in
+    /// `0..=1`, to `0..=1`, clamping the values outside.
+    ///
+    /// Unlike [`Self::normalize`], a few outlying texels do not limit the contrast. Returns the
+    /// mapping of every channel. Integer formats are rounded to nearest.
+    pub fn stretch(&mut self, low: f32, high: f32) -> Vec<ScaleBias> {
+        let scale_biases = (0..self.num_channels)
+            .into_par_iter()
+            .map(|channel| {
+                let values = self.sorted_values(channel);
+                ScaleBias::from_range(quantile(&values, low), quantile(&values, high))
+            })
+            .collect::<Vec<_>>();
+
+        self.data = self.map_values(self.format(), Quantization::Round, |channel, value| {
+            scale_biases[channel].apply(value)
+        });
+        scale_biases
+    }
+
+    /// Remaps every channel so that the cumulative distribution of its values follows
+    /// `target_cdf`, a non-decreasing function from `0..=1` to `0..=1`.
+    ///
+    /// The mapping is not linear, so every channel gets a curve instead: the original value at
+    /// 256 evenly spaced values from 0 to 1, to be undone with a lookup table. Integer formats
+    /// are rounded to nearest. Constant channels are left unchanged.
+    pub fn match_histogram(&mut self, target_cdf: impl Fn(f32) -> f32 + Sync) -> Vec<[f32; 256]> {
+        let sorted_values = (0..self.num_channels)
+            .into_par_iter()
+            .map(|channel| self.sorted_values(channel))
+            .collect::<Vec<_>>();
+
+        self.data = self.map_values(self.format(), Quantization::Round, |channel, value| {
+            let values = &sorted_values[channel];
+            if values.first() == values.last() {
+                // Leave constant channels unchanged
+                value
+            } else {
+                inverse_cdf(&target_cdf, cdf(values, value))
+            }
+        });
+
+        sorted_values
+            .iter()
+            .map(|values| std::array::from_fn(|i| quantile(values, target_cdf(i as f32 / 255.0))))
+            .collect()
+    }
+
+    /// Spreads the values of every channel evenly over `0..=1`, see [`Self::match_histogram`].
+    pub fn equalize(&mut self) -> Vec<[f32; 256]> {
+        self.match_histogram(|value| value)
+    }
+}
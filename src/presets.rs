@@ -0,0 +1,137 @@
+use crate::{
+    recipe::dilate, DetailAlpha, DetailChannels, GenerationDesc, NoiseNode, PerlinWorleyMode,
+    Recipe, TileableCloudNoise, WeightedNode,
+};
+
+/// Shape and detail textures for one cloud renderer, as baked by [`CloudPreset::bake`].
+pub struct CloudTextures {
+    pub shape: TileableCloudNoise,
+    pub detail: TileableCloudNoise,
+}
+
+/// Noise textures matching the inputs of published real-time cloud renderers.
+///
+/// The channel meanings of every preset are listed on its variant.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CloudPreset {
+    /// SebH's TileableVolumeNoise, following GPU Pro 7: Real Time Volumetric Cloudscapes. These
+    /// are the textures returned by [`TileableCloudNoise::cloud_shape_and_erosion_texture`] and
+    /// [`TileableCloudNoise::details_texture`].
+    ///
+    /// Shape, 128³: `perlin_worley`, `worley_fbm_0`, `worley_fbm_1`, `worley_fbm_2`.
+    /// Detail, 32³: `worley_fbm_0`, `worley_fbm_1`, `worley_fbm_2`, `unused` (always 1).
+    SebH,
+    /// The Real-Time Volumetric Cloudscapes of Horizon: Zero Dawn (Schneider, 2015), where the
+    /// Perlin-Worley channel is Perlin noise dilated by Worley FBM.
+    ///
+    /// Shape, 128³: `perlin_worley`, `worley_fbm_0`, `worley_fbm_1`, `worley_fbm_2`.
+    /// Detail, 32³: `worley_fbm_0`, `worley_fbm_1`, `worley_fbm_2`.
+    Schneider2015,
+    /// Nubis: Authoring Real-Time Volumetric Cloudscapes with the Decima Engine (Schneider, 2017),
+    /// where the low and high frequency noise are each combined into a single channel ahead of
+    /// time.
+    ///
+    /// Shape, 128³: `low_frequency_noise`, the Perlin-Worley channel dilated by the weighted
+    /// Worley FBM channels.
+    /// Detail, 32³: `high_frequency_noise`, the weighted Worley FBM channels.
+    Nubis2017,
+    /// Physically Based Sky, Atmosphere and Cloud Rendering in Frostbite (Hillaire, 2016), which
+    /// uses the same noise as [`Self::SebH`] without the unused detail channel.
+    ///
+    /// Shape, 128³: `perlin_worley`, `worley_fbm_0`, `worley_fbm_1`, `worley_fbm_2`.
+    /// Detail, 32³: `worley_fbm_0`, `worley_fbm_1`, `worley_fbm_2`.
+    Frostbite2016,
+    /// [`Self::Frostbite2016`] at half the resolution and frequencies, for low-end mobile devices.
+    ///
+    /// Shape, 64³: `perlin_worley`, `worley_fbm_0`, `worley_fbm_1`, `worley_fbm_2`.
+    /// Detail, 16³: `worley_fbm_0`, `worley_fbm_1`, `worley_fbm_2`.
+    Mobile,
+}
+
+// Sum of the channels of `recipe` with the given weights
+fn combine(recipe: Recipe, weights: &[f32]) -> NoiseNode {
+    NoiseNode::WeightedSum(
+        recipe
+            .channels
+            .into_iter()
+            .zip(weights)
+            .map(|(channel, &weight)| WeightedNode {
+                weight,
+                node: channel.node,
+            })
+            .collect(),
+    )
+}
+
+fn details_without_unused_channel() -> Recipe {
+    Recipe::details_with(&DetailChannels {
+        alpha: DetailAlpha::Omitted,
+        ..Default::default()
+    })
+}
+
+fn schneider_shape() -> Recipe {
+    Recipe::cloud_shape_and_erosion_with(PerlinWorleyMode::SchneiderDilated)
+}
+
+impl CloudPreset {
+    pub fn shape_recipe(self) -> Recipe {
+        match self {
+            Self::SebH | Self::Frostbite2016 => Recipe::cloud_shape_and_erosion(),
+            Self::Schneider2015 => schneider_shape(),
+            Self::Nubis2017 => {
+                let mut shape = schneider_shape();
+                let perlin_worley = shape.channels.remove(0).node;
+                let low_frequency_fbm = combine(shape, &[0.625, 0.25, 0.125]);
+
+                Recipe {
+                    channels: vec![Recipe::channel(
+                        "low_frequency_noise",
+                        dilate(perlin_worley, low_frequency_fbm),
+                    )],
+                }
+            }
+            Self::Mobile => Recipe::cloud_shape_and_erosion().with_frequencies_scaled(0.5),
+        }
+    }
+
+    pub fn detail_recipe(self) -> Recipe {
+        match self {
+            Self::SebH => Recipe::details(),
+            Self::Schneider2015 | Self::Frostbite2016 => details_without_unused_channel(),
+            Self::Nubis2017 => Recipe {
+                channels: vec![Recipe::channel(
+                    "high_frequency_noise",
+                    combine(details_without_unused_channel(), &[0.625, 0.25, 0.125]),
+                )],
+            },
+            Self::Mobile => details_without_unused_channel().with_frequencies_scaled(0.5),
+        }
+    }
+
+    /// Settings for baking [`Self::shape_recipe`] at the resolution of the preset.
+    pub fn shape_desc(self) -> GenerationDesc {
+        match self {
+            Self::Mobile => GenerationDesc::new(64),
+            _ => GenerationDesc::new(128),
+        }
+    }
+
+    /// Settings for baking [`Self::detail_recipe`] at the resolution of the preset.
+    pub fn detail_desc(self) -> GenerationDesc {
+        match self {
+            Self::Mobile => GenerationDesc::new(16),
+            _ => GenerationDesc::new(32),
+        }
+    }
+
+    /// Bakes both textures with the default settings of the preset.
+    pub fn bake(self) -> CloudTextures {
+        CloudTextures {
+            shape: TileableCloudNoise::from_recipe(&self.shape_recipe(), &self.shape_desc())
+                .expect("a new control is never cancelled"),
+            detail: TileableCloudNoise::from_recipe(&self.detail_recipe(), &self.detail_desc())
+                .expect("a new control is never cancelled"),
+        }
+    }
+}
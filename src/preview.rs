@@ -0,0 +1,193 @@
+use std::{fs, path::Path};
+
+use image::{
+    error::{ParameterError, ParameterErrorKind},
+    GrayImage, ImageError, ImageResult, Luma,
+};
+
+use crate::TileableCloudNoise;
+
+/// How the slices of a volume are arranged when flattened into a 2D image.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum SliceLayout {
+    /// All slices side by side in a single row.
+    #[default]
+    Strip,
+    /// Slices in a square-ish grid, filled row by row.
+    Atlas,
+}
+
+impl SliceLayout {
+    /// Number of slice columns and rows used to lay out `slice_count` slices.
+    pub fn grid(self, slice_count: u32) -> (u32, u32) {
+        match self {
+            Self::Strip => (slice_count, 1),
+            Self::Atlas => {
+                let columns = ((slice_count as f32).sqrt().ceil() as u32).max(1);
+                (columns, slice_count.div_ceil(columns))
+            }
+        }
+    }
+
+    /// Resolution of the cubic volume stored in a `width` x `height` image with this layout.
+    pub fn resolution(self, width: u32, height: u32) -> Option<u32> {
+        (1..=width.min(height)).find(|&res| {
+            let (columns, rows) = self.grid(res);
+            columns * res == width && rows * res == height
+        })
+    }
+}
+
+fn dimension_mismatch() -> ImageError {
+    ImageError::Parameter(ParameterError::from_kind(
+        ParameterErrorKind::DimensionMismatch,
+    ))
+}
+
+fn to_gray(value: f32) -> Luma<u8> {
+    Luma([(value.clamp(0.0, 1.0) * 255.0).round() as u8])
+}
+
+impl TileableCloudNoise {
+    /// Writes every channel as its own grayscale image named `{name}_{channel_name}.png`, with
+    /// the slices arranged according to `layout`.
+    pub fn write_channel_pngs(
+        &self,
+        dir: impl AsRef<Path>,
+        name: &str,
+        layout: SliceLayout,
+    ) -> ImageResult<()> {
+        let dir = dir.as_ref();
+        fs::create_dir_all(dir)?;
+
+        let res = self.resolution;
+        let (columns, rows) = layout.grid(res);
+
+        for (channel, channel_name) in (0..self.num_channels).zip(&self.channel_names) {
+            let mut image = GrayImage::new(columns * res, rows * res);
+            for z in 0..res {
+                let (offset_x, offset_y) = ((z % columns) * res, (z / columns) * res);
+                for y in 0..res {
+                    for x in 0..res {
+                        let value = self.sample(x, y, z, channel);
+                        image.put_pixel(offset_x + x, offset_y + y, to_gray(value));
+                    }
+                }
+            }
+            image.save(dir.join(format!("{}_{}.png", name, channel_name)))?;
+        }
+
+        Ok(())
+    }
+
+    /// Writes the XY, XZ and YZ planes through the middle of the volume as `{name}_xy.png`,
+    /// `{name}_xz.png` and `{name}_yz.png`, with the channels side by side in grayscale.
+    ///
+    /// Each plane is repeated `repeat` times along both axes; a `repeat` of 2 makes any seams
+    /// at the tile boundaries easy to spot.
+    pub fn write_cross_sections(
+        &self,
+        dir: impl AsRef<Path>,
+        name: &str,
+        repeat: u32,
+    ) -> ImageResult<()> {
+        let dir = dir.as_ref();
+        fs::create_dir_all(dir)?;
+
+        let res = self.resolution;
+        let mid = res / 2;
+        let panel = res * repeat;
+
+        for plane in ["xy", "xz", "yz"] {
+            let mut image = GrayImage::new(panel * self.num_channels, panel);
+            for channel in 0..self.num_channels {
+                for v in 0..panel {
+                    for u in 0..panel {
+                        let (a, b) = (u % res, v % res);
+                        let value = match plane {
+                            "xy" => self.sample(a, b, mid, channel),
+                            "xz" => self.sample(a, mid, b, channel),
+                            _ => self.sample(mid, a, b, channel),
+                        };
+                        image.put_pixel(channel * panel + u, v, to_gray(value));
+                    }
+                }
+            }
+            image.save(dir.join(format!("{}_{}.png", name, plane)))?;
+        }
+
+        Ok(())
+    }
+
+    /// Reads the RGBA8 strip written alongside the built-in textures when the `images` feature
+    /// is enabled, where every image row holds one complete slice.
+    ///
+    /// PNG does not store channel names, so the channels are named `r`, `g`, `b` and `a`.
+    pub fn read_png_strip(path: impl AsRef<Path>) -> ImageResult<Self> {
+        let image = image::open(path)?.into_rgba8();
+        let resolution = image.height();
+        if image.width() != resolution * resolution {
+            return Err(dimension_mismatch());
+        }
+
+        Ok(Self {
+            data: image.into_raw(),
+            resolution,
+            num_channels: 4,
+            bytes_per_channel: 1,
+            channel_names: ["r", "g", "b", "a"].map(String::from).to_vec(),
+        })
+    }
+
+    /// Reads the grayscale images written by [`Self::write_channel_pngs`] back into an 8-bit
+    /// volume with the given channels.
+    pub fn read_channel_pngs(
+        dir: impl AsRef<Path>,
+        name: &str,
+        channel_names: &[&str],
+        layout: SliceLayout,
+    ) -> ImageResult<Self> {
+        let dir = dir.as_ref();
+        let images = channel_names
+            .iter()
+            .map(|channel_name| {
+                image::open(dir.join(format!("{}_{}.png", name, channel_name)))
+                    .map(|image| image.into_luma8())
+            })
+            .collect::<ImageResult<Vec<_>>>()?;
+
+        let first = images.first().ok_or_else(dimension_mismatch)?;
+        let (width, height) = first.dimensions();
+        let resolution = layout
+            .resolution(width, height)
+            .filter(|_| {
+                images
+                    .iter()
+                    .all(|image| image.dimensions() == (width, height))
+            })
+            .ok_or_else(dimension_mismatch)?;
+        let (columns, _) = layout.grid(resolution);
+
+        let mut data = Vec::with_capacity((resolution.pow(3) as usize) * images.len());
+        for z in 0..resolution {
+            let (offset_x, offset_y) = ((z % columns) * resolution, (z / columns) * resolution);
+            for y in 0..resolution {
+                for x in 0..resolution {
+                    data.extend(
+                        images
+                            .iter()
+                            .map(|image| image.get_pixel(offset_x + x, offset_y + y)[0]),
+                    );
+                }
+            }
+        }
+
+        Ok(Self {
+            data,
+            resolution,
+            num_channels: images.len() as u32,
+            bytes_per_channel: 1,
+            channel_names: channel_names.iter().map(|name| name.to_string()).collect(),
+        })
+    }
+}
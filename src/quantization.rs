@@ -0,0 +1,209 @@
+use std::sync::OnceLock;
+
+/// How values are rounded to the integer [`TexelFormat`](crate::TexelFormat)s.
+///
+/// The dithering modes add a threshold pattern before truncating. The patterns repeat every 4
+/// (ordered) or 16 (blue noise) texels, or at the largest period dividing the resolution, so
+/// dithered textures keep tiling.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Quantization {
+    /// Rounds towards zero, biasing values down by half a step. This is how the reference
+    /// textures are quantized.
+    #[default]
+    Truncate,
+    /// Rounds to the nearest value.
+    Round,
+    /// Dithers with a 4×4×4 Bayer matrix.
+    OrderedDither,
+    /// Dithers with a 16×16×16 blue noise pattern computed with the void-and-cluster method.
+    BlueNoiseDither,
+}
+
+// Offsets added to the scaled values before truncating them, tiled over the volume.
+pub(crate) struct Quantizer {
+    thresholds: Vec<f32>,
+    period: u32,
+}
+
+impl Quantizer {
+    pub(crate) fn new(quantization: Quantization, resolution: u32) -> Self {
+        match quantization {
+            Quantization::Truncate => Self::constant(0.0),
+            Quantization::Round => Self::constant(0.5),
+            Quantization::OrderedDither => {
+                let period = 1 << resolution.trailing_zeros().min(2);
+                Self::from_ranks(&bayer_ranks(period), period)
+            }
+            Quantization::BlueNoiseDither => {
+                let period = gcd(resolution, 16);
+                Self::from_ranks(cached_void_and_cluster_ranks(period), period)
+            }
+        }
+    }
+
+    fn constant(threshold: f32) -> Self {
+        Self {
+            thresholds: vec![threshold],
+            period: 1,
+        }
+    }
+
+    fn from_ranks(ranks: &[u32], period: u32) -> Self {
+        let count = ranks.len() as f32;
+        Self {
+            thresholds: ranks
+                .iter()
+                .map(|&rank| (rank as f32 + 0.5) / count)
+                .collect(),
+            period,
+        }
+    }
+
+    pub(crate) fn threshold(&self, x: u32, y: u32, z: u32) -> f32 {
+        let period = self.period;
+        self.thresholds[(((z % period) * period + y % period) * period + x % period) as usize]
+    }
+}
+
+fn gcd(a: u32, b: u32) -> u32 {
+    if b == 0 {
+        a
+    } else {
+        gcd(b, a % b)
+    }
+}
+
+// Ranks of a recursive 3D Bayer matrix: every level visits the corners of a 2×2×2 cube in
+// pairs of opposite corners.
+fn bayer_ranks(period: u32) -> Vec<u32> {
+    // indexed by `z << 2 | y << 1 | x`
+    const CUBE: [u32; 8] = [0, 7, 5, 2, 3, 4, 6, 1];
+
+    let levels = period.trailing_zeros();
+    let mut ranks = Vec::with_capacity(period.pow(3) as usize);
+    for z in 0..period {
+        for y in 0..period {
+            for x in 0..period {
+                ranks.push((0..levels).fold(0, |rank, level| {
+                    let corner =
+                        ((z >> level) & 1) << 2 | ((y >> level) & 1) << 1 | (x >> level) & 1;
+                    rank + CUBE[corner as usize] * 8u32.pow(levels - 1 - level)
+                }));
+            }
+        }
+    }
+    ranks
+}
+
+// `void_and_cluster_ranks` of the periods dividing 16, computed once as the largest one takes a
+// noticeable time, while every bake and region quantizes with it.
+fn cached_void_and_cluster_ranks(period: u32) -> &'static [u32] {
+    static RANKS: [OnceLock<Vec<u32>>; 5] = [
+        OnceLock::new(),
+        OnceLock::new(),
+        OnceLock::new(),
+        OnceLock::new(),
+        OnceLock::new(),
+    ];
+    debug_assert!(period.is_power_of_two() && period <= 16);
+    RANKS[period.trailing_zeros() as usize].get_or_init(|| void_and_cluster_ranks(period))
+}
+
+// Ranks of a tileable blue noise pattern, following Ulichney's void-and-cluster method with a
+// toroidal Gaussian filter.
+fn void_and_cluster_ranks(period: u32) -> Vec<u32> {
+    let count = period.pow(3) as usize;
+    let offset = |a: usize, b: usize| {
+        let coords = |i: usize| {
+            let i = i as u32;
+            [i % period, (i / period) % period, i / (period * period)]
+        };
+        let (a, b) = (coords(a), coords(b));
+        let axis = |i: usize| {
+            let d = (a[i] + period - b[i]) % period;
+            d.min(period - d)
+        };
+        ((axis(2) * period + axis(1)) * period + axis(0)) as usize
+    };
+
+    // Filter weight for every wrapped offset
+    let sigma = 1.5f32;
+    let mut filter = vec![0.0f32; count];
+    for (i, weight) in filter.iter_mut().enumerate() {
+        let i = i as u32;
+        let (x, y, z) = (i % period, (i / period) % period, i / (period * period));
+        if x <= period / 2 && y <= period / 2 && z <= period / 2 {
+            let distance_sq = (x * x + y * y + z * z) as f32;
+            *weight = (-distance_sq / (2.0 * sigma * sigma)).exp();
+        }
+    }
+
+    let mut pattern = vec![false; count];
+    let mut energy = vec![0.0f32; count];
+    let toggle = |pattern: &mut [bool], energy: &mut [f32], index: usize| {
+        pattern[index] = !pattern[index];
+        let sign = if pattern[index] { 1.0 } else { -1.0 };
+        for (i, value) in energy.iter_mut().enumerate() {
+            *value += sign * filter[offset(i, index)];
+        }
+    };
+    let tightest_cluster = |pattern: &[bool], energy: &[f32]| {
+        (0..count)
+            .filter(|&i| pattern[i])
+            .max_by(|&a, &b| energy[a].total_cmp(&energy[b]))
+            .unwrap()
+    };
+    let largest_void = |pattern: &[bool], energy: &[f32]| {
+        (0..count)
+            .filter(|&i| !pattern[i])
+            .min_by(|&a, &b| energy[a].total_cmp(&energy[b]))
+            .unwrap()
+    };
+
+    // Initial pattern: a tenth of the texels picked with a fixed hash, then spread out by
+    // moving the tightest cluster to the largest void until it stops improving.
+    let initial_count = (count / 10).max(1);
+    let mut state = 0x9e37_79b9u32;
+    let mut placed = 0;
+    while placed < initial_count {
+        state ^= state << 13;
+        state ^= state >> 17;
+        state ^= state << 5;
+        let index = state as usize % count;
+        if !pattern[index] {
+            toggle(&mut pattern, &mut energy, index);
+            placed += 1;
+        }
+    }
+    if initial_count < count {
+        loop {
+            let cluster = tightest_cluster(&pattern, &energy);
+            toggle(&mut pattern, &mut energy, cluster);
+            let void = largest_void(&pattern, &energy);
+            if void == cluster {
+                toggle(&mut pattern, &mut energy, cluster);
+                break;
+            }
+            toggle(&mut pattern, &mut energy, void);
+        }
+    }
+
+    let mut ranks = vec![0u32; count];
+
+    // Remove the initial points from the tightest cluster down to rank 0
+    let (mut removed_pattern, mut removed_energy) = (pattern.clone(), energy.clone());
+    for rank in (0..initial_count).rev() {
+        let cluster = tightest_cluster(&removed_pattern, &removed_energy);
+        toggle(&mut removed_pattern, &mut removed_energy, cluster);
+        ranks[cluster] = rank as u32;
+    }
+
+    // Fill the largest voids with the remaining ranks
+    for rank in initial_count..count {
+        let void = largest_void(&pattern, &energy);
+        toggle(&mut pattern, &mut energy, void);
+        ranks[void] = rank as u32;
+    }
+
+    ranks
+}
@@ -0,0 +1,73 @@
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{TexelFormat, TileableCloudNoise};
+
+// Everything needed to interpret the raw texel data, stored next to it as JSON
+#[derive(Serialize, Deserialize)]
+struct Sidecar {
+    resolution: u32,
+    num_channels: u32,
+    bytes_per_channel: u32,
+    channel_names: Vec<String>,
+}
+
+fn sidecar_path(path: &Path) -> PathBuf {
+    path.with_extension("json")
+}
+
+impl TileableCloudNoise {
+    /// Writes [`Self::data`] unmodified to `path`, and its layout to a JSON sidecar file next to
+    /// it with the extension replaced by `.json`.
+    pub fn write_raw(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        let path = path.as_ref();
+        let sidecar = Sidecar {
+            resolution: self.resolution,
+            num_channels: self.num_channels,
+            bytes_per_channel: self.bytes_per_channel,
+            channel_names: self.channel_names.clone(),
+        };
+
+        fs::write(sidecar_path(path), serde_json::to_string_pretty(&sidecar)?)?;
+        fs::write(path, &self.data)
+    }
+
+    /// Reads a volume written by [`Self::write_raw`].
+    pub fn read_raw(path: impl AsRef<Path>) -> io::Result<Self> {
+        let path = path.as_ref();
+        let sidecar: Sidecar = serde_json::from_slice(&fs::read(sidecar_path(path))?)?;
+        let data = fs::read(path)?;
+
+        // Sizes that do not fit in memory cannot match the data either
+        let expected_len = [
+            sidecar.resolution,
+            sidecar.resolution,
+            sidecar.resolution,
+            sidecar.num_channels,
+            sidecar.bytes_per_channel,
+        ]
+        .into_iter()
+        .try_fold(1usize, |len, size| len.checked_mul(size as usize));
+        if expected_len != Some(data.len())
+            || sidecar.channel_names.len() != sidecar.num_channels as usize
+            || TexelFormat::from_bytes_per_channel(sidecar.bytes_per_channel).is_none()
+        {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "raw data does not match its sidecar",
+            ));
+        }
+
+        Ok(Self {
+            data,
+            resolution: sidecar.resolution,
+            num_channels: sidecar.num_channels,
+            bytes_per_channel: sidecar.bytes_per_channel,
+            channel_names: sidecar.channel_names,
+        })
+    }
+}
@@ -0,0 +1,861 @@
+use std::borrow::Cow;
+
+use glam::Vec3;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    threads::BakePool, Cancelled, GenerationDesc, PerlinBackend, PerlinTable, Tileable3dNoise,
+    TileableCloudNoise, WorleyGrid,
+};
+
+/// How the Perlin-Worley channel of [`Recipe::cloud_shape_and_erosion_with`] combines Perlin
+/// noise with Worley FBM.
+///
+/// GPU Pro 7: Real Time Volumetric Cloudscapes describes the combination in its text, while its
+/// figure 4.7 seems to show a different one.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum PerlinWorleyMode {
+    /// Perlin noise remapped between Worley FBM as minimum and 1.0 as maximum, as described in
+    /// the text of p.101. This is the channel of the reference texture.
+    #[default]
+    TextDescription,
+    /// Worley FBM remapped between 0.0 as minimum and Perlin noise as maximum, closer to figure
+    /// 4.7.
+    Figure4_7,
+    /// Perlin noise dilated by Worley FBM, i.e. remapped from `worley_fbm - 1.0..1.0` to
+    /// `0.0..1.0`, as in The Real-Time Volumetric Cloudscapes of Horizon: Zero Dawn.
+    SchneiderDilated,
+}
+
+/// How octaves above the Nyquist frequency of the resolution, `resolution / 2` cycles per volume,
+/// are removed from a [`Recipe`], see [`Recipe::band_limited`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum BandLimit {
+    /// Keeps every octave, as the reference textures do.
+    #[default]
+    Off,
+    /// Drops octaves above the Nyquist frequency.
+    Drop,
+    /// Fades octaves out smoothly from half the Nyquist frequency, dropping them at the Nyquist
+    /// frequency.
+    Fade,
+}
+
+impl BandLimit {
+    // Factor applied to the weight of an octave at `frequency`
+    fn octave_factor(self, frequency: f32, nyquist: f32) -> f32 {
+        match self {
+            Self::Off => 1.0,
+            Self::Drop => {
+                if frequency > nyquist {
+                    0.0
+                } else {
+                    1.0
+                }
+            }
+            Self::Fade => {
+                let t = ((frequency - 0.5 * nyquist) / (0.5 * nyquist)).clamp(0.0, 1.0);
+                1.0 - t * t * (3.0 - 2.0 * t)
+            }
+        }
+    }
+
+    // Applies `octave_factor` to `weights` of octaves at `frequencies`, scaling the remaining
+    // weights to keep their sum. Returns `None` if no weight changes.
+    fn limit_weights(
+        self,
+        weights: &[f32],
+        frequencies: impl Iterator<Item = f32>,
+        nyquist: f32,
+    ) -> Option<Vec<f32>> {
+        let frequencies = frequencies.collect::<Vec<_>>();
+        let factors = frequencies
+            .iter()
+            .map(|&frequency| self.octave_factor(frequency, nyquist))
+            .collect::<Vec<_>>();
+        if factors.iter().all(|&factor| factor == 1.0) {
+            return None;
+        }
+
+        let sum = weights.iter().sum::<f32>();
+        let mut limited = weights
+            .iter()
+            .zip(&factors)
+            .map(|(weight, factor)| weight * factor)
+            .collect::<Vec<_>>();
+        let limited_sum = limited.iter().sum::<f32>();
+        if limited_sum == 0.0 {
+            // Keep the lowest octave rather than nothing
+            let lowest = frequencies
+                .iter()
+                .enumerate()
+                .min_by(|(_, a), (_, b)| a.total_cmp(b))
+                .map_or(0, |(i, _)| i);
+            limited.fill(0.0);
+            limited[lowest] = sum;
+        } else {
+            for weight in &mut limited {
+                *weight *= sum / limited_sum;
+            }
+        }
+        Some(limited)
+    }
+}
+
+/// Worley octaves making up the FBM channels of [`Recipe::details_with`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum DetailOctaves {
+    /// Three octaves doubling the cell count, starting at 2 and doubling for every channel.
+    ///
+    /// Octaves with a cell count of 32 or more are dropped, as they are at the texel frequency
+    /// of the 32³ reference texture and look like white noise. This is the scheme of the
+    /// reference texture.
+    #[default]
+    PowerOfTwo,
+    /// Two neighbouring octaves weighted 0.75 and 0.25, out of Worley noise with cell counts 4,
+    /// 7, 10, 13 and so on.
+    NonPowerOfTwo,
+}
+
+/// Contents of the channel following the FBM channels of [`Recipe::details_with`].
+#[derive(Clone, Debug, Default, PartialEq)]
+pub enum DetailAlpha {
+    /// A channel named `unused`, always 1. This is the channel of the reference texture.
+    #[default]
+    Unused,
+    /// No channel.
+    Omitted,
+    /// One more FBM channel, continuing the octaves.
+    NextFbm,
+    /// A channel named `alpha` computed by the given node.
+    Node(NoiseNode),
+}
+
+/// Channels of the detail texture built by [`Recipe::details_with`].
+///
+/// The default describes the reference texture.
+#[derive(Clone, Debug, PartialEq)]
+pub struct DetailChannels {
+    pub octaves: DetailOctaves,
+    /// Number of Worley FBM channels, named `worley_fbm_0`, `worley_fbm_1` and so on.
+    pub fbm_count: u32,
+    pub alpha: DetailAlpha,
+}
+
+impl Default for DetailChannels {
+    fn default() -> Self {
+        Self {
+            octaves: DetailOctaves::PowerOfTwo,
+            fbm_count: 3,
+            alpha: DetailAlpha::Unused,
+        }
+    }
+}
+
+impl DetailOctaves {
+    fn fbm(self, index: u32) -> NoiseNode {
+        match self {
+            Self::PowerOfTwo => {
+                let cell_count = 2.0 * 2f32.powi(index as i32);
+                // cell_count=32 is just noise due to sampling frequency=texel frequency. So only take into account lower frequencies for FBM
+                let weights = if cell_count * 4.0 < 32.0 {
+                    vec![0.625, 0.25, 0.125]
+                } else if cell_count * 2.0 < 32.0 {
+                    vec![0.75, 0.25]
+                } else {
+                    vec![1.0]
+                };
+                NoiseNode::WorleyFbm {
+                    cell_count,
+                    weights,
+                }
+            }
+            Self::NonPowerOfTwo => NoiseNode::WeightedSum(
+                [0.75, 0.25]
+                    .into_iter()
+                    .zip(index..)
+                    .map(|(weight, octave)| WeightedNode {
+                        weight,
+                        node: NoiseNode::Invert(
+                            NoiseNode::Worley {
+                                cell_count: (4 + 3 * octave) as f32,
+                            }
+                            .boxed(),
+                        ),
+                    })
+                    .collect(),
+            ),
+        }
+    }
+}
+
+/// A node in the expression tree that computes the value of one channel at a texel.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum NoiseNode {
+    Constant(f32),
+    /// Tileable Perlin FBM, see [`Tileable3dNoise::perlin_noise`].
+    Perlin {
+        frequency: f32,
+        octave_count: u32,
+    },
+    /// Distance to the closest feature point, see [`Tileable3dNoise::worley_noise`].
+    Worley {
+        cell_count: f32,
+    },
+    /// Weighted sum of inverted Worley noise, doubling `cell_count` for every weight.
+    WorleyFbm {
+        cell_count: f32,
+        weights: Vec<f32>,
+    },
+    /// `1.0 - input`
+    Invert(Box<NoiseNode>),
+    /// Linearly maps `input` from `from_min..from_max` to `to_min..to_max`.
+    Remap {
+        input: Box<NoiseNode>,
+        from_min: Box<NoiseNode>,
+        from_max: Box<NoiseNode>,
+        to_min: Box<NoiseNode>,
+        to_max: Box<NoiseNode>,
+    },
+    WeightedSum(Vec<WeightedNode>),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct WeightedNode {
+    pub weight: f32,
+    pub node: NoiseNode,
+}
+
+/// One output channel of a [`Recipe`].
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct RecipeChannel {
+    pub name: String,
+    pub node: NoiseNode,
+}
+
+/// Serializable description of a texture, evaluated with [`TileableCloudNoise::from_recipe`].
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Recipe {
+    pub channels: Vec<RecipeChannel>,
+}
+
+// Number of texels evaluated at once, one per SIMD lane
+const BATCH_LEN: usize = 8;
+
+// Channels of the built-in textures share Worley octaves, which are by far the most expensive
+// nodes, so every batch of texels keeps the ones it already evaluated, and looks up the feature
+// points of every cell count in a grid computed once per volume. Perlin noise is looked up in
+// `perlin_table` with the table backend. Noise is evaluated in 4D at `time` when set.
+struct NoiseCache<'a> {
+    grids: &'a [WorleyGrid],
+    perlin_table: Option<&'a PerlinTable>,
+    time: Option<f32>,
+    entries: [(f32, [f32; BATCH_LEN]); 16],
+    len: usize,
+}
+
+impl<'a> NoiseCache<'a> {
+    fn new(
+        grids: &'a [WorleyGrid],
+        perlin_table: Option<&'a PerlinTable>,
+        time: Option<f32>,
+    ) -> Self {
+        Self {
+            grids,
+            perlin_table,
+            time,
+            entries: Default::default(),
+            len: 0,
+        }
+    }
+
+    fn perlin_noise(
+        &self,
+        p: &[Vec3; BATCH_LEN],
+        frequency: f32,
+        octave_count: u32,
+        seed: u32,
+    ) -> [f32; BATCH_LEN] {
+        match (self.perlin_table, self.time) {
+            (Some(table), None) => p.map(|p| table.perlin_noise(p, frequency, octave_count)),
+            (Some(table), Some(time)) => {
+                p.map(|p| table.perlin_noise_4d(p, time, frequency, octave_count))
+            }
+            (None, None) => Tileable3dNoise::perlin_noise_x8(*p, frequency, octave_count, seed),
+            (None, Some(time)) => {
+                Tileable3dNoise::perlin_noise_4d_x8(*p, time, frequency, octave_count, seed)
+            }
+        }
+    }
+
+    fn worley_noise(
+        &mut self,
+        p: &[Vec3; BATCH_LEN],
+        cell_count: f32,
+        seed: u32,
+    ) -> [f32; BATCH_LEN] {
+        if let Some(&(_, values)) = self.entries[..self.len]
+            .iter()
+            .find(|(cached_cell_count, _)| *cached_cell_count == cell_count)
+        {
+            return values;
+        }
+
+        let values = match self
+            .grids
+            .iter()
+            .find(|grid| grid.cell_count() == cell_count)
+        {
+            Some(grid) => grid.worley_noise_x8(*p),
+            None => match self.time {
+                None => p.map(|p| Tileable3dNoise::worley_noise_seeded(p, cell_count, seed)),
+                Some(time) => {
+                    p.map(|p| Tileable3dNoise::worley_noise_4d(p, time, cell_count, seed))
+                }
+            },
+        };
+        if self.len < self.entries.len() {
+            self.entries[self.len] = (cell_count, values);
+            self.len += 1;
+        }
+        values
+    }
+}
+
+// Applies `f` to every lane of the batches
+fn lanes<const M: usize>(
+    values: [[f32; BATCH_LEN]; M],
+    f: impl Fn([f32; M]) -> f32,
+) -> [f32; BATCH_LEN] {
+    std::array::from_fn(|lane| f(values.map(|values| values[lane])))
+}
+
+impl NoiseNode {
+    pub(crate) fn boxed(self) -> Box<Self> {
+        Box::new(self)
+    }
+
+    fn scale_frequencies(&mut self, factor: f32) {
+        match self {
+            Self::Constant(_) => {}
+            Self::Perlin { frequency, .. } => *frequency *= factor,
+            Self::Worley { cell_count } | Self::WorleyFbm { cell_count, .. } => {
+                *cell_count *= factor
+            }
+            Self::Invert(input) => input.scale_frequencies(factor),
+            Self::Remap {
+                input,
+                from_min,
+                from_max,
+                to_min,
+                to_max,
+            } => {
+                for node in [input, from_min, from_max, to_min, to_max] {
+                    node.scale_frequencies(factor);
+                }
+            }
+            Self::WeightedSum(nodes) => {
+                for node in nodes {
+                    node.node.scale_frequencies(factor);
+                }
+            }
+        }
+    }
+
+    // Adds the cell count of every Worley octave of the node to `cell_counts`
+    fn worley_cell_counts(&self, cell_counts: &mut Vec<f32>) {
+        let mut add = |cell_count: f32| {
+            if !cell_counts.contains(&cell_count) {
+                cell_counts.push(cell_count);
+            }
+        };
+        match self {
+            Self::Constant(_) | Self::Perlin { .. } => {}
+            Self::Worley { cell_count } => add(*cell_count),
+            Self::WorleyFbm {
+                cell_count,
+                weights,
+            } => {
+                for octave in 0..weights.len() {
+                    add(cell_count * 2f32.powi(octave as i32));
+                }
+            }
+            Self::Invert(input) => input.worley_cell_counts(cell_counts),
+            Self::Remap {
+                input,
+                from_min,
+                from_max,
+                to_min,
+                to_max,
+            } => {
+                for node in [input, from_min, from_max, to_min, to_max] {
+                    node.worley_cell_counts(cell_counts);
+                }
+            }
+            Self::WeightedSum(nodes) => {
+                for node in nodes {
+                    node.node.worley_cell_counts(cell_counts);
+                }
+            }
+        }
+    }
+
+    // Highest frequency of the node in cycles per volume, counting Worley cell counts as
+    // frequencies
+    fn max_frequency(&self) -> f32 {
+        match self {
+            Self::Constant(_) => 0.0,
+            Self::Perlin {
+                frequency,
+                octave_count,
+            } => frequency * 2f32.powi(octave_count.saturating_sub(1) as i32),
+            Self::Worley { cell_count } => *cell_count,
+            Self::WorleyFbm {
+                cell_count,
+                weights,
+            } => cell_count * 2f32.powi(weights.len().saturating_sub(1) as i32),
+            Self::Invert(input) => input.max_frequency(),
+            Self::Remap {
+                input,
+                from_min,
+                from_max,
+                to_min,
+                to_max,
+            } => [input, from_min, from_max, to_min, to_max]
+                .iter()
+                .map(|node| node.max_frequency())
+                .fold(0.0, f32::max),
+            Self::WeightedSum(nodes) => nodes
+                .iter()
+                .map(|node| node.node.max_frequency())
+                .fold(0.0, f32::max),
+        }
+    }
+
+    fn band_limit(&mut self, nyquist: f32, band_limit: BandLimit) {
+        match self {
+            Self::Constant(_) | Self::Worley { .. } => {}
+            Self::Perlin {
+                frequency,
+                octave_count,
+            } => {
+                // Octave weights of `Tileable3dNoise::perlin_noise`
+                let mut weight = 0.5f32;
+                let weights = (0..*octave_count)
+                    .map(|_| {
+                        let octave_weight = weight;
+                        weight *= weight;
+                        octave_weight
+                    })
+                    .collect::<Vec<_>>();
+                let frequencies =
+                    (0..*octave_count).map(|octave| *frequency * 2f32.powi(octave as i32));
+
+                if let Some(weights) = band_limit.limit_weights(&weights, frequencies, nyquist) {
+                    let kept = weights.iter().take_while(|&&weight| weight > 0.0).count() as u32;
+                    if band_limit == BandLimit::Drop {
+                        // Dropping the highest octaves keeps the normalized weights of the others
+                        *octave_count = kept.max(1);
+                    } else {
+                        let sum = weights.iter().sum::<f32>();
+                        *self = Self::WeightedSum(
+                            weights
+                                .iter()
+                                .enumerate()
+                                .filter(|(_, &weight)| weight > 0.0)
+                                .map(|(octave, &weight)| WeightedNode {
+                                    weight: weight / sum,
+                                    node: Self::Perlin {
+                                        frequency: *frequency * 2f32.powi(octave as i32),
+                                        octave_count: 1,
+                                    },
+                                })
+                                .collect(),
+                        );
+                    }
+                }
+            }
+            Self::WorleyFbm {
+                cell_count,
+                weights,
+            } => {
+                let frequencies =
+                    (0..weights.len()).map(|octave| *cell_count * 2f32.powi(octave as i32));
+                if let Some(limited) = band_limit.limit_weights(weights, frequencies, nyquist) {
+                    *weights = limited;
+                    while weights.len() > 1 && weights.last() == Some(&0.0) {
+                        weights.pop();
+                    }
+                }
+            }
+            Self::Invert(input) => input.band_limit(nyquist, band_limit),
+            Self::Remap {
+                input,
+                from_min,
+                from_max,
+                to_min,
+                to_max,
+            } => {
+                for node in [input, from_min, from_max, to_min, to_max] {
+                    node.band_limit(nyquist, band_limit);
+                }
+            }
+            Self::WeightedSum(nodes) => {
+                for node in nodes.iter_mut() {
+                    node.node.band_limit(nyquist, band_limit);
+                }
+                // Constant terms, such as the offset of `dilate`, have no frequency and are kept
+                // as they are. Rescaling the other terms only keeps their sum if all their
+                // weights are positive, otherwise they are left to their own limits.
+                let is_noise = |node: &WeightedNode| !matches!(node.node, Self::Constant(_));
+                let weights = nodes
+                    .iter()
+                    .filter(|node| is_noise(node))
+                    .map(|node| node.weight)
+                    .collect::<Vec<_>>();
+                if weights.is_empty() || weights.iter().any(|&weight| weight <= 0.0) {
+                    return;
+                }
+                let frequencies = nodes
+                    .iter()
+                    .filter(|node| is_noise(node))
+                    .map(|node| node.node.max_frequency());
+                if let Some(limited) = band_limit.limit_weights(&weights, frequencies, nyquist) {
+                    let noise_nodes = nodes.iter_mut().filter(|node| is_noise(node));
+                    for (node, weight) in noise_nodes.zip(limited) {
+                        node.weight = weight;
+                    }
+                    nodes.retain(|node| !is_noise(node) || node.weight != 0.0);
+                }
+            }
+        }
+    }
+
+    fn evaluate(
+        &self,
+        p: &[Vec3; BATCH_LEN],
+        seed: u32,
+        cache: &mut NoiseCache,
+    ) -> [f32; BATCH_LEN] {
+        match self {
+            Self::Constant(value) => [*value; BATCH_LEN],
+            Self::Perlin {
+                frequency,
+                octave_count,
+            } => cache.perlin_noise(p, *frequency, *octave_count, seed),
+            Self::Worley { cell_count } => cache.worley_noise(p, *cell_count, seed),
+            Self::WorleyFbm {
+                cell_count,
+                weights,
+            } => weights
+                .iter()
+                .enumerate()
+                .map(|(octave, weight)| {
+                    let octave_cell_count = cell_count * 2f32.powi(octave as i32);
+                    let values = cache.worley_noise(p, octave_cell_count, seed);
+                    lanes([values], |[value]| (1.0 - value) * weight)
+                })
+                .fold([0.0; BATCH_LEN], |sum, values| {
+                    lanes([sum, values], |[sum, value]| sum + value)
+                }),
+            Self::Invert(input) => lanes([input.evaluate(p, seed, cache)], |[value]| 1.0 - value),
+            Self::Remap {
+                input,
+                from_min,
+                from_max,
+                to_min,
+                to_max,
+            } => lanes(
+                [
+                    input.evaluate(p, seed, cache),
+                    from_min.evaluate(p, seed, cache),
+                    from_max.evaluate(p, seed, cache),
+                    to_min.evaluate(p, seed, cache),
+                    to_max.evaluate(p, seed, cache),
+                ],
+                |[value, from_min, from_max, to_min, to_max]| {
+                    TileableCloudNoise::remap(value, from_min, from_max, to_min, to_max)
+                },
+            ),
+            Self::WeightedSum(nodes) => nodes
+                .iter()
+                .map(|node| {
+                    let values = node.node.evaluate(p, seed, cache);
+                    lanes([values], |[value]| value * node.weight)
+                })
+                .fold([0.0; BATCH_LEN], |sum, values| {
+                    lanes([sum, values], |[sum, value]| sum + value)
+                }),
+        }
+    }
+}
+
+// `input` remapped from `dilation - 1.0..1.0` to `0.0..1.0`
+pub(crate) fn dilate(input: NoiseNode, dilation: NoiseNode) -> NoiseNode {
+    NoiseNode::Remap {
+        input: input.boxed(),
+        from_min: NoiseNode::WeightedSum(vec![
+            WeightedNode {
+                weight: 1.0,
+                node: dilation,
+            },
+            WeightedNode {
+                weight: -1.0,
+                node: NoiseNode::Constant(1.0),
+            },
+        ])
+        .boxed(),
+        from_max: NoiseNode::Constant(1.0).boxed(),
+        to_min: NoiseNode::Constant(0.0).boxed(),
+        to_max: NoiseNode::Constant(1.0).boxed(),
+    }
+}
+
+impl Recipe {
+    pub(crate) fn channel(name: &str, node: NoiseNode) -> RecipeChannel {
+        RecipeChannel {
+            name: name.to_string(),
+            node,
+        }
+    }
+
+    /// Multiplies every Perlin frequency and Worley cell count by `factor`, to keep the noise at
+    /// the same scale relative to the texels when changing the resolution by `factor`.
+    ///
+    /// Worley noise only tiles for whole cell counts.
+    pub fn with_frequencies_scaled(mut self, factor: f32) -> Self {
+        for channel in &mut self.channels {
+            channel.node.scale_frequencies(factor);
+        }
+        self
+    }
+
+    /// Removes the octaves of Perlin noise, Worley FBM and weighted sums whose frequency is
+    /// too high for `resolution`, as selected by `band_limit`, scaling the weights of the
+    /// remaining ones to keep their sum. Worley cell counts are taken as frequencies.
+    ///
+    /// Noise outside of octaves, such as Worley noise that is not part of a sum, is kept.
+    pub fn band_limited(mut self, resolution: u32, band_limit: BandLimit) -> Self {
+        if band_limit != BandLimit::Off {
+            let nyquist = resolution as f32 / 2.0;
+            for channel in &mut self.channels {
+                channel.node.band_limit(nyquist, band_limit);
+            }
+        }
+        self
+    }
+
+    /// The recipe of [`TileableCloudNoise::cloud_shape_and_erosion_texture`].
+    pub fn cloud_shape_and_erosion() -> Self {
+        Self::cloud_shape_and_erosion_with(PerlinWorleyMode::TextDescription)
+    }
+
+    /// Same as [`Self::cloud_shape_and_erosion`], with the Perlin-Worley channel computed as
+    /// selected by `mode`.
+    pub fn cloud_shape_and_erosion_with(mode: PerlinWorleyMode) -> Self {
+        // As SebH mentions in the reference material, frequency values should be reduced if using a smaller resolution.
+        let frequence_mul = [2.0f32, 8.0f32, 14.0f32, 20.0f32, 26.0f32, 32.0f32]; // special weight for perlin-worley
+
+        // Cloud base shape (will be used to generate Perlin-Worley noise in the shader)
+        // Note: all channels could be combined once here to reduce memory bandwith requirements.
+
+        // Perlin FBM noise
+        let perlin_noise = NoiseNode::Perlin {
+            frequency: 8.0,
+            octave_count: 3,
+        };
+
+        let cell_count = 4f32;
+        let worley_fbm = NoiseNode::WeightedSum(
+            [0.625f32, 0.25f32, 0.125f32]
+                .into_iter()
+                .zip(frequence_mul)
+                .map(|(weight, frequency)| WeightedNode {
+                    weight,
+                    node: NoiseNode::Invert(
+                        NoiseNode::Worley {
+                            cell_count: cell_count * frequency,
+                        }
+                        .boxed(),
+                    ),
+                })
+                .collect(),
+        );
+
+        // Perlin Worley is based on description in GPU Pro 7: Real Time Volumetric Cloudscapes.
+        // However, it is not clear the text and the image are matching: images does not seem to match what the result from the description in text would give.
+        // Also there are a lot of fudge factor in the code, e.g. * 0.2, so it is really up to you to fine the formula you like.
+
+        let perlin_worley = match mode {
+            // mapping perlin noise in between worley as minimum and 1.0 as maximum (as described in text of p.101 of GPU Pro 7)
+            PerlinWorleyMode::TextDescription => NoiseNode::Remap {
+                input: perlin_noise.boxed(),
+                from_min: NoiseNode::Constant(0.0).boxed(),
+                from_max: NoiseNode::Constant(1.0).boxed(),
+                to_min: worley_fbm.boxed(),
+                to_max: NoiseNode::Constant(1.0).boxed(),
+            },
+            // Matches better what figure 4.7 (not the following up text description p.101). Maps worley between newMin as 0 and perlin as maximum.
+            PerlinWorleyMode::Figure4_7 => NoiseNode::Remap {
+                input: worley_fbm.boxed(),
+                from_min: NoiseNode::Constant(0.0).boxed(),
+                from_max: NoiseNode::Constant(1.0).boxed(),
+                to_min: NoiseNode::Constant(0.0).boxed(),
+                to_max: perlin_noise.boxed(),
+            },
+            PerlinWorleyMode::SchneiderDilated => dilate(perlin_noise, worley_fbm),
+        };
+
+        // Three frequency of Worley FBM noise, starting at cell_count * 2
+        // cell_count=2 -> half the frequency of texel, we should not go further (with cellCount = 32 and texture size = 64)
+        let worley_fbm_0 = NoiseNode::WorleyFbm {
+            cell_count: cell_count * 2.0,
+            weights: vec![0.625, 0.25, 0.125],
+        };
+        let worley_fbm_1 = NoiseNode::WorleyFbm {
+            cell_count: cell_count * 4.0,
+            weights: vec![0.625, 0.25, 0.125],
+        };
+        // cell_count=4 -> worleyNoise5 is just noise due to sampling frequency=texel frequency. So only take into account 2 frequencies for FBM
+        let worley_fbm_2 = NoiseNode::WorleyFbm {
+            cell_count: cell_count * 8.0,
+            weights: vec![0.75, 0.25],
+        };
+
+        Self {
+            channels: vec![
+                Self::channel("perlin_worley", perlin_worley),
+                Self::channel("worley_fbm_0", worley_fbm_0),
+                Self::channel("worley_fbm_1", worley_fbm_1),
+                Self::channel("worley_fbm_2", worley_fbm_2),
+            ],
+        }
+    }
+
+    /// The recipe of [`TileableCloudNoise::details_texture`].
+    pub fn details() -> Self {
+        Self::details_with(&DetailChannels::default())
+    }
+
+    /// Same as [`Self::details`], with the channels described by `channels`.
+    pub fn details_with(channels: &DetailChannels) -> Self {
+        // Detail texture behing different frequency of Worley noise
+        // Note: all channels could be combined once here to reduce memory bandwith requirements.
+        let fbm_count = match channels.alpha {
+            DetailAlpha::NextFbm => channels.fbm_count + 1,
+            _ => channels.fbm_count,
+        };
+        let mut recipe = Self {
+            channels: (0..fbm_count)
+                .map(|index| {
+                    Self::channel(
+                        &format!("worley_fbm_{}", index),
+                        channels.octaves.fbm(index),
+                    )
+                })
+                .collect(),
+        };
+
+        match &channels.alpha {
+            DetailAlpha::Unused => recipe
+                .channels
+                .push(Self::channel("unused", NoiseNode::Constant(1.0))),
+            DetailAlpha::Node(node) => recipe.channels.push(Self::channel("alpha", node.clone())),
+            DetailAlpha::Omitted | DetailAlpha::NextFbm => {}
+        }
+        recipe
+    }
+}
+
+// A recipe ready to be evaluated: band limited, with the Worley grids and Perlin table it uses
+pub(crate) struct PreparedRecipe<'a> {
+    recipe: Cow<'a, Recipe>,
+    seed: u32,
+    time: Option<f32>,
+    grids: Vec<WorleyGrid>,
+    perlin_table: Option<PerlinTable>,
+}
+
+impl<'a> PreparedRecipe<'a> {
+    pub(crate) fn new(recipe: &'a Recipe, desc: &GenerationDesc, pool: &BakePool) -> Self {
+        let recipe = if desc.band_limit == BandLimit::Off {
+            Cow::Borrowed(recipe)
+        } else {
+            Cow::Owned(
+                recipe
+                    .clone()
+                    .band_limited(desc.resolution, desc.band_limit),
+            )
+        };
+
+        let mut cell_counts = Vec::new();
+        for channel in &recipe.channels {
+            channel.node.worley_cell_counts(&mut cell_counts);
+        }
+        // The grids are computed in parallel too
+        let grids = pool.install(|| {
+            cell_counts
+                .into_iter()
+                .filter_map(|cell_count| match desc.time {
+                    None => WorleyGrid::new(cell_count, desc.seed),
+                    Some(time) => WorleyGrid::at_time(cell_count, desc.seed, time),
+                })
+                .collect()
+        });
+        let perlin_table = match desc.perlin_backend {
+            PerlinBackend::Glm => None,
+            PerlinBackend::Table => Some(PerlinTable::new(desc.seed)),
+        };
+
+        Self {
+            recipe,
+            seed: desc.seed,
+            time: desc.time,
+            grids,
+            perlin_table,
+        }
+    }
+
+    pub(crate) fn channel_names(&self) -> Vec<String> {
+        self.recipe
+            .channels
+            .iter()
+            .map(|channel| channel.name.clone())
+            .collect()
+    }
+
+    // Writes the value of every channel at every point of `coords` to `values`
+    pub(crate) fn evaluate(&self, coords: &[Vec3], values: &mut [f32]) {
+        let num_channels = self.recipe.channels.len();
+        for (batch, coords) in coords.chunks(BATCH_LEN).enumerate() {
+            // Pad the last batch with its last texel
+            let p = std::array::from_fn(|lane| coords[lane.min(coords.len() - 1)]);
+            let mut cache = NoiseCache::new(&self.grids, self.perlin_table.as_ref(), self.time);
+            for (c, channel) in self.recipe.channels.iter().enumerate() {
+                let channel_values = channel.node.evaluate(&p, self.seed, &mut cache);
+                for (lane, value) in channel_values[..coords.len()].iter().enumerate() {
+                    values[(batch * BATCH_LEN + lane) * num_channels + c] = *value;
+                }
+            }
+        }
+    }
+}
+
+impl TileableCloudNoise {
+    /// Evaluates every channel of `recipe` over the volume described by `desc`, after
+    /// [`Recipe::band_limited`] with `desc.band_limit`, in the pool selected by `desc.threads`.
+    /// Returns [`Cancelled`] if `desc.control` is cancelled.
+    pub fn from_recipe(recipe: &Recipe, desc: &GenerationDesc) -> Result<Self, Cancelled> {
+        let pool = desc.threads.pool();
+        let recipe = PreparedRecipe::new(recipe, desc, &pool);
+        Self::generate(desc, &pool, recipe.channel_names(), |coords, values| {
+            recipe.evaluate(coords, values)
+        })
+    }
+}
@@ -0,0 +1,74 @@
+use std::ops::Range;
+
+use glam::Vec3;
+
+use crate::{
+    recipe::PreparedRecipe, rows_from_fn, Cancelled, GenerationDesc, Recipe, TileableCloudNoise,
+};
+
+/// Axis-aligned box of texels of a volume, see [`TileableCloudNoise::region_from_recipe`].
+///
+/// As the volumes tile, texels outside `0..resolution` wrap around, so a region may straddle the
+/// border of the volume or cover it more than once.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Region {
+    pub x: Range<i32>,
+    pub y: Range<i32>,
+    pub z: Range<i32>,
+}
+
+impl Region {
+    /// The whole volume.
+    pub fn full(resolution: u32) -> Self {
+        let range = 0..resolution as i32;
+        Self {
+            x: range.clone(),
+            y: range.clone(),
+            z: range,
+        }
+    }
+
+    /// Number of texels along each axis.
+    pub fn size(&self) -> [u32; 3] {
+        [&self.x, &self.y, &self.z].map(|range| range.len() as u32)
+    }
+}
+
+impl TileableCloudNoise {
+    /// Generates `region` of the volume [`Self::from_recipe`] generates with `desc`, and returns
+    /// its data laid out as [`Self::data`], with [`Region::size`] texels along each axis.
+    ///
+    /// The bytes are identical to the same texels of the whole volume, so regions generated
+    /// separately, for example on different machines, assemble into the same volume. Returns
+    /// [`Cancelled`] if `desc.control` is cancelled.
+    pub fn region_from_recipe(
+        recipe: &Recipe,
+        desc: &GenerationDesc,
+        region: &Region,
+    ) -> Result<Vec<u8>, Cancelled> {
+        let pool = desc.threads.pool();
+        let recipe = PreparedRecipe::new(recipe, desc, &pool);
+        Self::generate_region(
+            desc,
+            &pool,
+            region,
+            recipe.channel_names().len() as u32,
+            |coords, values| recipe.evaluate(coords, values),
+        )
+    }
+
+    /// Same as [`Self::region_from_recipe`] for the volume [`Self::from_fn`] generates.
+    pub fn region_from_fn<const N: usize>(
+        desc: &GenerationDesc,
+        texel: impl Fn(Vec3) -> [f32; N] + Sync,
+        region: &Region,
+    ) -> Result<Vec<u8>, Cancelled> {
+        Self::generate_region(
+            desc,
+            &desc.threads.pool(),
+            region,
+            N as u32,
+            rows_from_fn(texel),
+        )
+    }
+}
@@ -0,0 +1,124 @@
+use rustfft::{num_complex::Complex, FftPlanner};
+
+use crate::TileableCloudNoise;
+
+/// Fraction of the energy above the Nyquist frequency above which
+/// [`PowerSpectrum::is_aliased`] reports a channel.
+const ALIASING_THRESHOLD: f32 = 0.05;
+
+/// Radially averaged power spectrum of one channel, as returned by
+/// [`TileableCloudNoise::power_spectrum`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct PowerSpectrum {
+    /// Mean power of the frequencies at every distance from the origin, in cycles per volume
+    /// rounded to the nearest integer. The mean value of the channel is removed first.
+    pub power: Vec<f32>,
+    /// Fraction of the energy at frequencies above the Nyquist frequency of the resolution,
+    /// `resolution / 2` cycles per volume, which only fit along the diagonals of the volume.
+    pub energy_above_nyquist: f32,
+}
+
+impl PowerSpectrum {
+    /// Whether more than 5% of the energy is above the Nyquist frequency.
+    ///
+    /// Smooth noise has almost none, while noise with features around the size of a texel, such
+    /// as Worley noise with as many cells as texels, looks like white noise and spreads its
+    /// energy evenly over all frequencies, half of which are above the Nyquist frequency.
+    pub fn is_aliased(&self) -> bool {
+        self.energy_above_nyquist > ALIASING_THRESHOLD
+    }
+}
+
+// In-place FFT of every line of `data` along the axis with the given stride between texels
+fn fft_axis(
+    data: &mut [Complex<f32>],
+    resolution: usize,
+    stride: usize,
+    planner: &mut FftPlanner<f32>,
+) {
+    let fft = planner.plan_fft_forward(resolution);
+    let mut line = vec![Complex::default(); resolution];
+
+    for start in 0..resolution.pow(3) {
+        // Visit every line once, from the texel where its coordinate along the axis is 0
+        if (start / stride) % resolution != 0 {
+            continue;
+        }
+        for (i, value) in line.iter_mut().enumerate() {
+            *value = data[start + i * stride];
+        }
+        fft.process(&mut line);
+        for (i, value) in line.iter().enumerate() {
+            data[start + i * stride] = *value;
+        }
+    }
+}
+
+impl TileableCloudNoise {
+    /// Computes the power spectrum of `channel`.
+    ///
+    /// As the volume tiles, its discrete Fourier transform has no leakage from the borders.
+    pub fn power_spectrum(&self, channel: u32) -> PowerSpectrum {
+        let res = self.resolution as usize;
+
+        let mut data = Vec::with_capacity(res.pow(3));
+        for z in 0..self.resolution {
+            for y in 0..self.resolution {
+                for x in 0..self.resolution {
+                    data.push(Complex::new(self.sample(x, y, z, channel), 0.0));
+                }
+            }
+        }
+        let mean = data.iter().map(|value| value.re as f64).sum::<f64>() / data.len() as f64;
+        for value in &mut data {
+            value.re -= mean as f32;
+        }
+
+        let mut planner = FftPlanner::new();
+        for stride in [1, res, res * res] {
+            fft_axis(&mut data, res, stride, &mut planner);
+        }
+
+        // Signed frequency of the index along an axis
+        let frequency = |i: usize| {
+            if i <= res / 2 {
+                i as f32
+            } else {
+                i as f32 - res as f32
+            }
+        };
+        let nyquist = res as f32 / 2.0;
+        let bin_count = (nyquist * 3f32.sqrt()).round() as usize + 1;
+        let mut energy = vec![0.0f64; bin_count];
+        let mut count = vec![0u32; bin_count];
+        let (mut total_energy, mut energy_above_nyquist) = (0.0f64, 0.0f64);
+
+        for (i, value) in data.iter().enumerate() {
+            let (x, y, z) = (i % res, (i / res) % res, i / (res * res));
+            let radius =
+                (frequency(x).powi(2) + frequency(y).powi(2) + frequency(z).powi(2)).sqrt();
+            let power = value.norm_sqr() as f64;
+
+            let bin = radius.round() as usize;
+            energy[bin] += power;
+            count[bin] += 1;
+            total_energy += power;
+            if radius > nyquist {
+                energy_above_nyquist += power;
+            }
+        }
+
+        PowerSpectrum {
+            power: energy
+                .iter()
+                .zip(&count)
+                .map(|(&energy, &count)| (energy / count.max(1) as f64) as f32)
+                .collect(),
+            energy_above_nyquist: if total_energy > 0.0 {
+                (energy_above_nyquist / total_energy) as f32
+            } else {
+                0.0
+            },
+        }
+    }
+}
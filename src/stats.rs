@@ -0,0 +1,78 @@
+use rayon::prelude::{IntoParallelIterator, ParallelIterator};
+
+use crate::TileableCloudNoise;
+
+/// Fraction of saturated texels above which [`ChannelStats::is_saturated`] reports a channel.
+const SATURATION_THRESHOLD: f64 = 0.01;
+
+/// Distribution of the values of one channel, as returned by [`TileableCloudNoise::stats`].
+///
+/// Values are normalized to `0..=1` as by [`TileableCloudNoise::sample`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct ChannelStats {
+    pub min: f32,
+    pub max: f32,
+    pub mean: f32,
+    pub variance: f32,
+    /// Number of texels in 256 equal bins over `0..=1`, the values outside being counted in the
+    /// first and last bins. For [`TexelFormat::Unorm8`](crate::TexelFormat::Unorm8), bin `i`
+    /// counts the texels stored as `i`.
+    pub histogram: [u64; 256],
+    /// Number of texels at or beyond 0 or 1.
+    pub saturated_count: u64,
+}
+
+impl ChannelStats {
+    /// Whether more than 1% of the texels are saturated at 0 or 1, as happens when a remap
+    /// pushes values out of range. Constant channels are never reported.
+    pub fn is_saturated(&self) -> bool {
+        let texel_count: u64 = self.histogram.iter().sum();
+        self.min != self.max
+            && self.saturated_count as f64 > texel_count as f64 * SATURATION_THRESHOLD
+    }
+}
+
+impl TileableCloudNoise {
+    /// Computes the distribution of the values of every channel.
+    pub fn stats(&self) -> Vec<ChannelStats> {
+        let res = self.resolution;
+        (0..self.num_channels)
+            .into_par_iter()
+            .map(|channel| {
+                let mut stats = ChannelStats {
+                    min: f32::MAX,
+                    max: f32::MIN,
+                    mean: 0.0,
+                    variance: 0.0,
+                    histogram: [0; 256],
+                    saturated_count: 0,
+                };
+                let (mut sum, mut sum_sq) = (0.0f64, 0.0f64);
+
+                for z in 0..res {
+                    for y in 0..res {
+                        for x in 0..res {
+                            let value = self.sample(x, y, z, channel);
+                            stats.min = stats.min.min(value);
+                            stats.max = stats.max.max(value);
+                            sum += value as f64;
+                            sum_sq += value as f64 * value as f64;
+
+                            let bin = (value.clamp(0.0, 1.0) * 256.0) as usize;
+                            stats.histogram[bin.min(255)] += 1;
+                            if value <= 0.0 || value >= 1.0 {
+                                stats.saturated_count += 1;
+                            }
+                        }
+                    }
+                }
+
+                let texel_count = res.pow(3) as f64;
+                let mean = sum / texel_count;
+                stats.mean = mean as f32;
+                stats.variance = (sum_sq / texel_count - mean * mean).max(0.0) as f32;
+                stats
+            })
+            .collect()
+    }
+}
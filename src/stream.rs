@@ -0,0 +1,125 @@
+use std::io::{self, Write};
+
+use glam::Vec3;
+
+use crate::{
+    recipe::PreparedRecipe, rows_from_fn, Cancelled, GenerationDesc, Recipe, Region, TexelFormat,
+    TileableCloudNoise,
+};
+
+/// Container written by [`TileableCloudNoise::write_recipe_stream`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum StreamFormat {
+    /// The texel data alone, in the layout of [`TileableCloudNoise::data`].
+    #[default]
+    Raw,
+    /// A NRRD file: a short text header describing the layout, followed by the raw texel data.
+    /// It opens in ParaView and 3D Slicer, and the channel names are stored in the
+    /// `channel_names` field, separated by spaces.
+    Nrrd,
+}
+
+fn write_nrrd_header(
+    writer: &mut impl Write,
+    desc: &GenerationDesc,
+    channel_names: &[String],
+) -> io::Result<()> {
+    let res = desc.resolution;
+    writeln!(writer, "NRRD0004")?;
+    writeln!(writer, "# Tileable volume noise")?;
+    let kind = match desc.format {
+        TexelFormat::Unorm8 => "uint8",
+        TexelFormat::Unorm16 => "uint16",
+        TexelFormat::Float32 => "float",
+    };
+    writeln!(writer, "type: {kind}")?;
+    // Single channel volumes are plain 3D volumes
+    if channel_names.len() == 1 {
+        writeln!(writer, "dimension: 3")?;
+        writeln!(writer, "sizes: {res} {res} {res}")?;
+        writeln!(writer, "kinds: domain domain domain")?;
+    } else {
+        writeln!(writer, "dimension: 4")?;
+        writeln!(writer, "sizes: {} {res} {res} {res}", channel_names.len())?;
+        writeln!(writer, "kinds: list domain domain domain")?;
+    }
+    if desc.format != TexelFormat::Unorm8 {
+        writeln!(writer, "endian: little")?;
+    }
+    writeln!(writer, "encoding: raw")?;
+    writeln!(writer, "channel_names:={}", channel_names.join(" "))?;
+    writeln!(writer)
+}
+
+impl TileableCloudNoise {
+    /// Same as [`Self::from_fn`], passing every slice to `on_slice` in order with its index
+    /// instead of keeping the volume in memory. Slices are laid out as in [`Self::data`].
+    ///
+    /// Only as many slices as there are threads in the pool are held at once. Stops at the
+    /// first error returned by `on_slice`, and returns [`Cancelled`] converted to `E` if
+    /// `desc.control` is cancelled.
+    pub fn stream_fn<const N: usize, E: From<Cancelled>>(
+        desc: &GenerationDesc,
+        texel: impl Fn(Vec3) -> [f32; N] + Sync,
+        mut on_slice: impl FnMut(u32, &[u8]) -> Result<(), E>,
+    ) -> Result<(), E> {
+        let pool = desc.threads.pool();
+        Self::generate_slices(
+            desc,
+            &pool,
+            &Region::full(desc.resolution),
+            N as u32,
+            pool.current_num_threads() as u32,
+            rows_from_fn(texel),
+            |s, slice| on_slice(s, &slice),
+        )
+    }
+
+    /// Same as [`Self::from_recipe`], passing every slice to `on_slice` in order with its index
+    /// instead of keeping the volume in memory. Slices are laid out as in [`Self::data`].
+    ///
+    /// Only as many slices as there are threads in the pool are held at once. Stops at the
+    /// first error returned by `on_slice`, and returns [`Cancelled`] converted to `E` if
+    /// `desc.control` is cancelled.
+    pub fn stream_recipe<E: From<Cancelled>>(
+        recipe: &Recipe,
+        desc: &GenerationDesc,
+        mut on_slice: impl FnMut(u32, &[u8]) -> Result<(), E>,
+    ) -> Result<(), E> {
+        let pool = desc.threads.pool();
+        let recipe = PreparedRecipe::new(recipe, desc, &pool);
+        Self::generate_slices(
+            desc,
+            &pool,
+            &Region::full(desc.resolution),
+            recipe.channel_names().len() as u32,
+            pool.current_num_threads() as u32,
+            |coords, values| recipe.evaluate(coords, values),
+            |s, slice| on_slice(s, &slice),
+        )
+    }
+
+    /// Streams `recipe` to `writer` in `format` with [`Self::stream_recipe`], so that volumes
+    /// too large for memory can be baked to disk.
+    ///
+    /// Cancellation is reported as an error of kind [`io::ErrorKind::Other`] wrapping
+    /// [`Cancelled`].
+    pub fn write_recipe_stream(
+        recipe: &Recipe,
+        desc: &GenerationDesc,
+        format: StreamFormat,
+        mut writer: impl Write,
+    ) -> io::Result<()> {
+        if format == StreamFormat::Nrrd {
+            let channel_names = recipe
+                .channels
+                .iter()
+                .map(|channel| channel.name.clone())
+                .collect::<Vec<_>>();
+            write_nrrd_header(&mut writer, desc, &channel_names)?;
+        }
+
+        Self::stream_recipe(recipe, desc, |_, slice| writer.write_all(slice))?;
+        writer.flush()
+    }
+}
@@ -0,0 +1,94 @@
+use glam::Vec3;
+
+/// Placement of the samples of [`Supersampling`] in their strata.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum SamplePattern {
+    /// At the center of every stratum.
+    #[default]
+    Stratified,
+    /// At a random position in every stratum, different for every texel.
+    Jittered,
+}
+
+/// Weighting of the samples of [`Supersampling`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ReconstructionFilter {
+    /// Equal weights over the texel.
+    #[default]
+    Box,
+    /// Gaussian weights with a standard deviation of half a texel, over two texels.
+    Gaussian,
+}
+
+/// Averages several samples of the noise for every texel, filtering out the frequencies the
+/// resolution cannot represent at the cost of bake time.
+///
+/// The footprint of the filter is split in `samples_per_axis`³ strata with one sample each. The
+/// default takes a single sample at the texel, as the reference textures do.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Supersampling {
+    pub samples_per_axis: u32,
+    pub pattern: SamplePattern,
+    pub filter: ReconstructionFilter,
+}
+
+impl Default for Supersampling {
+    fn default() -> Self {
+        Self {
+            samples_per_axis: 1,
+            pattern: SamplePattern::Stratified,
+            filter: ReconstructionFilter::Box,
+        }
+    }
+}
+
+// PCG hash, to jitter every sample independently
+fn hash(mut value: u32) -> u32 {
+    value = value.wrapping_mul(747_796_405).wrapping_add(2_891_336_453);
+    let word = ((value >> ((value >> 28) + 4)) ^ value).wrapping_mul(277_803_737);
+    (word >> 22) ^ word
+}
+
+impl Supersampling {
+    // Replaces `samples` with the offset in texels and the normalized weight of every sample of
+    // `texel`
+    pub(crate) fn samples(&self, texel: [u32; 3], samples: &mut Vec<(Vec3, f32)>) {
+        let count = self.samples_per_axis.max(1);
+        let footprint = match self.filter {
+            ReconstructionFilter::Box => 1.0,
+            ReconstructionFilter::Gaussian => 2.0,
+        };
+
+        samples.clear();
+        let mut random = hash(texel[0] ^ hash(texel[1] ^ hash(texel[2])));
+        let mut position = |stratum: u32| {
+            let offset = match self.pattern {
+                SamplePattern::Stratified => 0.5,
+                SamplePattern::Jittered => {
+                    random = hash(random);
+                    random as f32 / (u32::MAX as f32 + 1.0)
+                }
+            };
+            ((stratum as f32 + offset) / count as f32 - 0.5) * footprint
+        };
+
+        for z in 0..count {
+            for y in 0..count {
+                for x in 0..count {
+                    let offset = Vec3::new(position(x), position(y), position(z));
+                    let weight = match self.filter {
+                        ReconstructionFilter::Box => 1.0,
+                        // exp(-d² / (2σ²)) with σ = 0.5
+                        ReconstructionFilter::Gaussian => (-2.0 * offset.length_squared()).exp(),
+                    };
+                    samples.push((offset, weight));
+                }
+            }
+        }
+
+        let weight_sum = samples.iter().map(|(_, weight)| weight).sum::<f32>();
+        for (_, weight) in samples.iter_mut() {
+            *weight /= weight_sum;
+        }
+    }
+}
@@ -0,0 +1,57 @@
+use std::sync::Arc;
+
+use rayon::{ThreadPool, ThreadPoolBuilder};
+
+/// Threads running a bake, see [`GenerationDesc::threads`](crate::GenerationDesc::threads).
+#[derive(Clone, Debug, Default)]
+pub enum Threads {
+    /// Rayon's global thread pool.
+    #[default]
+    Global,
+    /// A pool with this many threads, created for every bake. 0 picks rayon's default, one
+    /// thread per CPU.
+    Count(usize),
+    /// An existing pool, for example one shared by several bakes.
+    Pool(Arc<ThreadPool>),
+}
+
+impl Threads {
+    // Creates the pool of one bake
+    pub(crate) fn pool(&self) -> BakePool<'_> {
+        match self {
+            Self::Global => BakePool::Global,
+            Self::Count(count) => BakePool::Owned(
+                ThreadPoolBuilder::new()
+                    .num_threads(*count)
+                    .build()
+                    .expect("failed to spawn the threads of the pool"),
+            ),
+            Self::Pool(pool) => BakePool::Shared(pool),
+        }
+    }
+}
+
+pub(crate) enum BakePool<'a> {
+    Global,
+    Owned(ThreadPool),
+    Shared(&'a ThreadPool),
+}
+
+impl BakePool<'_> {
+    // Runs `op` in the pool, so that the parallel iterators it uses run there
+    pub(crate) fn install<R: Send>(&self, op: impl FnOnce() -> R + Send) -> R {
+        match self {
+            Self::Global => op(),
+            Self::Owned(pool) => pool.install(op),
+            Self::Shared(pool) => pool.install(op),
+        }
+    }
+
+    pub(crate) fn current_num_threads(&self) -> usize {
+        match self {
+            Self::Global => rayon::current_num_threads(),
+            Self::Owned(pool) => pool.current_num_threads(),
+            Self::Shared(pool) => pool.current_num_threads(),
+        }
+    }
+}
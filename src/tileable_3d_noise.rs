@@ -1,8 +1,470 @@
-use crate::glm_functions::{glm_mod_3, glm_perlin_vec4, lerp};
+use crate::glm_functions::{glm_mod_3, glm_perlin_lanes, glm_perlin_vec4, lerp, Lanes, Vec4x2};
 use glam::{Vec3, Vec4};
+use rayon::prelude::{IntoParallelIterator, ParallelIterator};
+
+/// Largest cell count for which [`WorleyGrid::new`] stores the feature points, 256³ of them.
+const MAX_GRID_CELL_COUNT: f32 = 256.0;
+
+/// Hash offset between consecutive cells along the time axis of
+/// [`Tileable3dNoise::worley_noise_4d`]. The feature points of time cell 0 are those of 3D Worley
+/// noise. It is larger than the hashes of the spatial cells, `x + 57 y + 113 z` for cell counts up
+/// to 256, so that no two cells share their feature point.
+const TIME_CELL_HASH_STEP: f32 = 65536.0;
+
+// Time cells visited around the time cell of a point
+const TIME_NEIGHBORS: [i32; 3] = [-1, 0, 1];
+
+/// The feature points of [`Tileable3dNoise::worley_noise_seeded`] for one cell count and seed,
+/// computed once to evaluate the noise at many points faster.
+///
+/// Every cell of Worley noise places its feature point with eight sine-based hashes, and every
+/// evaluation visits 27 cells. The grid looks them up instead, with identical results.
+pub struct WorleyGrid {
+    cell_count: f32,
+    seed: u32,
+    // Time of grids for `worley_noise_4d`
+    time: Option<f32>,
+    // Offset of the feature point of every cell, indexed by
+    // `((w * cell_count + z) * cell_count + y) * cell_count + x`, with one layer `w` for 3D noise
+    // and one per time neighbor for 4D noise
+    offsets: Vec<f32>,
+}
+
+impl WorleyGrid {
+    /// Computes the feature points of every cell, or returns `None` if `cell_count` is not a
+    /// whole number from 1 to 256.
+    pub fn new(cell_count: f32, seed: u32) -> Option<Self> {
+        Self::with_time(cell_count, seed, None)
+    }
+
+    /// Same as [`Self::new`] for [`Tileable3dNoise::worley_noise_4d`] at `time`, computing the
+    /// feature points of the cells around `time` along the time axis.
+    pub fn at_time(cell_count: f32, seed: u32, time: f32) -> Option<Self> {
+        Self::with_time(cell_count, seed, Some(time))
+    }
+
+    fn with_time(cell_count: f32, seed: u32, time: Option<f32>) -> Option<Self> {
+        if cell_count.fract() != 0.0 || !(1.0..=MAX_GRID_CELL_COUNT).contains(&cell_count) {
+            return None;
+        }
+
+        let count = cell_count as u32;
+        let hash_offset = Tileable3dNoise::hash_offset(seed);
+        let layer_hash_offsets = match time {
+            None => vec![hash_offset],
+            Some(time) => {
+                let time_base = (time * cell_count).floor();
+                TIME_NEIGHBORS
+                    .iter()
+                    .map(|&w| {
+                        let time_cell = Tileable3dNoise::wrap(time_base + w as f32, cell_count);
+                        hash_offset + TIME_CELL_HASH_STEP * time_cell
+                    })
+                    .collect()
+            }
+        };
+        // One task per slice of every layer
+        let offsets = (0..layer_hash_offsets.len() as u32 * count)
+            .into_par_iter()
+            .flat_map_iter(|layer_z| {
+                let hash_offset = layer_hash_offsets[(layer_z / count) as usize];
+                let z = layer_z % count;
+                (0..count * count).map(move |i| {
+                    let cell = Vec3::new((i % count) as f32, (i / count) as f32, z as f32);
+                    Tileable3dNoise::noise(cell, hash_offset)
+                })
+            })
+            .collect();
+
+        Some(Self {
+            cell_count,
+            seed,
+            time,
+            offsets,
+        })
+    }
+
+    pub fn cell_count(&self) -> f32 {
+        self.cell_count
+    }
+
+    pub fn seed(&self) -> u32 {
+        self.seed
+    }
+
+    /// Time of grids built with [`Self::at_time`].
+    pub fn time(&self) -> Option<f32> {
+        self.time
+    }
+
+    // Time neighbors of the layers of the grid
+    fn layers(&self) -> &'static [i32] {
+        match self.time {
+            None => &[0],
+            Some(_) => &TIME_NEIGHBORS,
+        }
+    }
+
+    /// Same as [`Tileable3dNoise::worley_noise_seeded`] with the cell count and seed of the grid,
+    /// or [`Tileable3dNoise::worley_noise_4d`] at the time of grids built with [`Self::at_time`].
+    pub fn worley_noise(&self, p: Vec3) -> f32 {
+        let count = self.cell_count as i32;
+        let p_cell = p * self.cell_count;
+        let base = p_cell.floor();
+        let time_cell = self.time.map(|time| time * self.cell_count);
+        let mut d = 1.0e10f32;
+
+        for (layer, &w) in self.layers().iter().enumerate() {
+            let layer_offsets = &self.offsets[layer * count.pow(3) as usize..];
+            for x in -1..=1 {
+                for y in -1..=1 {
+                    for z in -1..=1 {
+                        let tp = base + Vec3::new(x as f32, y as f32, z as f32);
+                        let cell = tp
+                            .as_ivec3()
+                            .to_array()
+                            .map(|i| i.rem_euclid(count) as usize);
+                        let offset = layer_offsets
+                            [(cell[2] * count as usize + cell[1]) * count as usize + cell[0]];
+                        let tp = p_cell - tp - offset;
+
+                        d = d.min(match time_cell {
+                            None => tp.dot(tp),
+                            Some(time_cell) => {
+                                let tw = time_cell - (time_cell.floor() + w as f32) - offset;
+                                tp.dot(tp) + tw * tw
+                            }
+                        });
+                    }
+                }
+            }
+        }
+
+        d.clamp(0.0, 1.0)
+    }
+
+    /// [`Self::worley_noise`] at four points at once, using SIMD lanes.
+    pub fn worley_noise_x4(&self, p: [Vec3; 4]) -> [f32; 4] {
+        self.worley_noise_lanes::<Vec4, 4>(p)
+    }
+
+    /// [`Self::worley_noise`] at eight points at once, using SIMD lanes.
+    pub fn worley_noise_x8(&self, p: [Vec3; 8]) -> [f32; 8] {
+        self.worley_noise_lanes::<Vec4x2, 8>(p)
+    }
+
+    fn worley_noise_lanes<L: Lanes, const N: usize>(&self, p: [Vec3; N]) -> [f32; N] {
+        debug_assert_eq!(L::COUNT, N);
+        let count = self.cell_count as i32;
+        let cell_count = L::splat(self.cell_count);
+        let p_cell = [
+            L::from_slice(&p.map(|p| p.x)) * cell_count,
+            L::from_slice(&p.map(|p| p.y)) * cell_count,
+            L::from_slice(&p.map(|p| p.z)) * cell_count,
+        ];
+        let base = p_cell.map(L::floor);
+        let time_cell = self.time.map(|time| time * self.cell_count);
+        let mut d = L::splat(1.0e10);
+
+        let mut coords = [[0.0f32; N]; 3];
+        let mut offsets = [0.0f32; N];
+        for (layer, &w) in self.layers().iter().enumerate() {
+            let layer_offsets = &self.offsets[layer * count.pow(3) as usize..];
+            for x in -1..=1 {
+                for y in -1..=1 {
+                    for z in -1..=1 {
+                        let neighbor = [x, y, z];
+                        let tp: [L; 3] =
+                            std::array::from_fn(|i| base[i] + L::splat(neighbor[i] as f32));
+
+                        // Gather the offsets of the cells of every lane
+                        for (tp, coords) in tp.iter().zip(&mut coords) {
+                            tp.write_to_slice(coords);
+                        }
+                        for (lane, offset) in offsets.iter_mut().enumerate() {
+                            let cell = coords.map(|coords| (coords[lane] as i32).rem_euclid(count));
+                            *offset = layer_offsets
+                                [((cell[2] * count + cell[1]) * count + cell[0]) as usize];
+                        }
+                        let offset = L::from_slice(&offsets);
+
+                        let tp: [L; 3] = std::array::from_fn(|i| p_cell[i] - tp[i] - offset);
+                        let distance = tp[0] * tp[0] + tp[1] * tp[1] + tp[2] * tp[2];
+                        d = d.min(match time_cell {
+                            None => distance,
+                            Some(time_cell) => {
+                                let tw =
+                                    L::splat(time_cell - (time_cell.floor() + w as f32)) - offset;
+                                distance + tw * tw
+                            }
+                        });
+                    }
+                }
+            }
+        }
+
+        let mut result = [0.0; N];
+        d.max(L::splat(0.0))
+            .min(L::splat(1.0))
+            .write_to_slice(&mut result);
+        result
+    }
+}
+
+/// Implementation of the gradient noise of [`NoiseNode::Perlin`](crate::NoiseNode::Perlin).
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum PerlinBackend {
+    /// 4D Perlin noise computed from polynomial hashes, as the reference textures are.
+    #[default]
+    Glm,
+    /// Perlin noise looked up in a [`PerlinTable`], faster to bake and simpler to match with
+    /// table-based shaders. It has the same period and range, but a different pattern. It is 4D
+    /// when animated with [`GenerationDesc::time`](crate::GenerationDesc::time).
+    Table,
+}
+
+/// Ken Perlin's reference permutation, used for seed 0.
+#[rustfmt::skip]
+const PERMUTATION: [u8; 256] = [
+    151, 160, 137, 91, 90, 15, 131, 13, 201, 95, 96, 53, 194, 233, 7, 225,
+    140, 36, 103, 30, 69, 142, 8, 99, 37, 240, 21, 10, 23, 190, 6, 148,
+    247, 120, 234, 75, 0, 26, 197, 62, 94, 252, 219, 203, 117, 35, 11, 32,
+    57, 177, 33, 88, 237, 149, 56, 87, 174, 20, 125, 136, 171, 168, 68, 175,
+    74, 165, 71, 134, 139, 48, 27, 166, 77, 146, 158, 231, 83, 111, 229, 122,
+    60, 211, 133, 230, 220, 105, 92, 41, 55, 46, 245, 40, 244, 102, 143, 54,
+    65, 25, 63, 161, 1, 216, 80, 73, 209, 76, 132, 187, 208, 89, 18, 169,
+    200, 196, 135, 130, 116, 188, 159, 86, 164, 100, 109, 198, 173, 186, 3, 64,
+    52, 217, 226, 250, 124, 123, 5, 202, 38, 147, 118, 126, 255, 82, 85, 212,
+    207, 206, 59, 227, 47, 16, 58, 17, 182, 189, 28, 42, 223, 183, 170, 213,
+    119, 248, 152, 2, 44, 154, 163, 70, 221, 153, 101, 155, 167, 43, 172, 9,
+    129, 22, 39, 253, 19, 98, 108, 110, 79, 113, 224, 232, 178, 185, 112, 104,
+    218, 246, 97, 228, 251, 34, 242, 193, 238, 210, 144, 12, 191, 179, 162, 241,
+    81, 51, 145, 235, 249, 14, 239, 107, 49, 192, 214, 31, 181, 199, 106, 157,
+    184, 84, 204, 176, 115, 121, 50, 45, 127, 4, 150, 254, 138, 236, 205, 93,
+    222, 114, 67, 29, 24, 72, 243, 141, 128, 195, 78, 66, 215, 61, 156, 180,
+];
+
+/// The 12 directions to the edges of a cube, normalized. Four of them are repeated as in
+/// Perlin's improved noise, so that a hash picks one with a mask.
+const GRADIENTS: [[f32; 3]; 16] = {
+    const H: f32 = std::f32::consts::FRAC_1_SQRT_2;
+    [
+        [H, H, 0.0],
+        [-H, H, 0.0],
+        [H, -H, 0.0],
+        [-H, -H, 0.0],
+        [H, 0.0, H],
+        [-H, 0.0, H],
+        [H, 0.0, -H],
+        [-H, 0.0, -H],
+        [0.0, H, H],
+        [0.0, -H, H],
+        [0.0, H, -H],
+        [0.0, -H, -H],
+        [H, H, 0.0],
+        [0.0, -H, H],
+        [-H, H, 0.0],
+        [0.0, -H, -H],
+    ]
+};
+
+/// The 32 directions to the edges of a tesseract, normalized, for the 4D noise of
+/// [`PerlinTable::perlin_noise_4d`].
+const GRADIENTS_4D: [[f32; 4]; 32] = {
+    const T: f32 = 0.577_350_26; // 1/√3
+    let mut gradients = [[0.0; 4]; 32];
+    let mut i = 0;
+    while i < 32 {
+        // One axis is 0, the signs of the three others come from the low bits
+        let zero_axis = i / 8;
+        let mut sign_bit = 0;
+        let mut axis = 0;
+        while axis < 4 {
+            if axis != zero_axis {
+                gradients[i][axis] = if (i >> sign_bit) & 1 == 0 { T } else { -T };
+                sign_bit += 1;
+            }
+            axis += 1;
+        }
+        i += 1;
+    }
+    gradients
+};
+
+/// Scales the noise from `-√3/2..=√3/2`, the range of 3D gradient noise with unit gradients,
+/// to `-1..=1`.
+const GRADIENT_NOISE_SCALE: f32 = 1.154_700_5;
+
+/// Permutation table of the [`PerlinBackend::Table`] backend for one seed.
+///
+/// The lattice coordinates wrap at the period before being hashed with the table, so the noise
+/// tiles for any whole frequency.
+pub struct PerlinTable {
+    seed: u32,
+    // Permutation repeated twice, to hash without wrapping the sums
+    permutation: [u8; 512],
+}
+
+impl PerlinTable {
+    /// Builds the table of `seed`: Ken Perlin's reference permutation for seed 0, shuffled for
+    /// other seeds.
+    pub fn new(seed: u32) -> Self {
+        let mut shuffled = PERMUTATION;
+        if seed != 0 {
+            // Fisher-Yates shuffle driven by a linear congruential generator
+            let mut state = seed;
+            for i in (1..shuffled.len()).rev() {
+                state = state.wrapping_mul(747_796_405).wrapping_add(2_891_336_453);
+                let j = ((state as u64 * (i as u64 + 1)) >> 32) as usize;
+                shuffled.swap(i, j);
+            }
+        }
+
+        let mut permutation = [0; 512];
+        permutation[..256].copy_from_slice(&shuffled);
+        permutation[256..].copy_from_slice(&shuffled);
+        Self { seed, permutation }
+    }
+
+    pub fn seed(&self) -> u32 {
+        self.seed
+    }
+
+    /// Same as [`Tileable3dNoise::perlin_noise_seeded`] with the seed of the table, using the
+    /// [`PerlinBackend::Table`] backend.
+    ///
+    /// Every octave repeats at its frequency rounded to the nearest whole number.
+    pub fn perlin_noise(&self, p: Vec3, mut frequency: f32, octave_count: u32) -> f32 {
+        let mut sum = 0.0;
+        let mut weight_sum = 0.0;
+        let mut weight = 0.5;
+
+        for _ in 0..octave_count {
+            let period = (frequency.round() as i32).max(1);
+            sum += self.gradient_noise(p * frequency, period) * weight;
+            weight_sum += weight;
+
+            weight *= weight;
+            frequency *= 2.0;
+        }
+
+        let noise = (sum / weight_sum) * 0.5 + 0.5;
+        noise.clamp(0.0, 1.0)
+    }
+
+    /// Same as [`Self::perlin_noise`], with `time` as 4th coordinate. The noise repeats every 1.0
+    /// in time as in space, so `time` from 0.0 to 1.0 animates it in a seamless loop.
+    pub fn perlin_noise_4d(
+        &self,
+        p: Vec3,
+        time: f32,
+        mut frequency: f32,
+        octave_count: u32,
+    ) -> f32 {
+        let mut sum = 0.0;
+        let mut weight_sum = 0.0;
+        let mut weight = 0.5;
+
+        for _ in 0..octave_count {
+            let period = (frequency.round() as i32).max(1);
+            let point = (p * frequency).extend(time * frequency);
+            sum += self.gradient_noise_4d(point, period) * weight;
+            weight_sum += weight;
+
+            weight *= weight;
+            frequency *= 2.0;
+        }
+
+        let noise = (sum / weight_sum) * 0.5 + 0.5;
+        noise.clamp(0.0, 1.0)
+    }
+
+    // Index of the gradient of a lattice point
+    fn hash(&self, cell: [i32; 3]) -> usize {
+        let p = &self.permutation;
+        let [x, y, z] = cell.map(|i| (i & 255) as usize);
+        p[p[p[x] as usize + y] as usize + z] as usize & 15
+    }
+
+    // One octave of noise in `-1..=1`, repeating every `period` along each axis
+    fn gradient_noise(&self, p: Vec3, period: i32) -> f32 {
+        let base = p.floor();
+        let f = p - base;
+        let base = base.as_ivec3();
+        // Quintic interpolation, as in the reference implementation
+        let u = f * f * f * (f * (f * 6.0 - Vec3::splat(15.0)) + Vec3::splat(10.0));
+
+        let corner = |x: i32, y: i32, z: i32| {
+            let cell = [base.x + x, base.y + y, base.z + z].map(|i| i.rem_euclid(period));
+            let gradient = Vec3::from(GRADIENTS[self.hash(cell)]);
+            gradient.dot(f - Vec3::new(x as f32, y as f32, z as f32))
+        };
+
+        let noise = lerp(
+            lerp(
+                lerp(corner(0, 0, 0), corner(1, 0, 0), u.x),
+                lerp(corner(0, 1, 0), corner(1, 1, 0), u.x),
+                u.y,
+            ),
+            lerp(
+                lerp(corner(0, 0, 1), corner(1, 0, 1), u.x),
+                lerp(corner(0, 1, 1), corner(1, 1, 1), u.x),
+                u.y,
+            ),
+            u.z,
+        );
+        noise * GRADIENT_NOISE_SCALE
+    }
+
+    // Index of the gradient of a 4D lattice point
+    fn hash_4d(&self, cell: [i32; 4]) -> usize {
+        let p = &self.permutation;
+        let [x, y, z, w] = cell.map(|i| (i & 255) as usize);
+        p[p[p[p[x] as usize + y] as usize + z] as usize + w] as usize & 31
+    }
+
+    // One octave of 4D noise in `-1..=1`, repeating every `period` along each axis. Unit
+    // gradients already keep 4D gradient noise in `-1..=1`.
+    fn gradient_noise_4d(&self, p: Vec4, period: i32) -> f32 {
+        let base = p.floor();
+        let f = p - base;
+        let base = base.as_ivec4();
+        let u = f * f * f * (f * (f * 6.0 - Vec4::splat(15.0)) + Vec4::splat(10.0));
+
+        let corner = |x: i32, y: i32, z: i32, w: i32| {
+            let cell =
+                [base.x + x, base.y + y, base.z + z, base.w + w].map(|i| i.rem_euclid(period));
+            let gradient = Vec4::from(GRADIENTS_4D[self.hash_4d(cell)]);
+            gradient.dot(f - Vec4::new(x as f32, y as f32, z as f32, w as f32))
+        };
+        // Noise of the cube at `w` along the time axis
+        let cube = |w: i32| {
+            lerp(
+                lerp(
+                    lerp(corner(0, 0, 0, w), corner(1, 0, 0, w), u.x),
+                    lerp(corner(0, 1, 0, w), corner(1, 1, 0, w), u.x),
+                    u.y,
+                ),
+                lerp(
+                    lerp(corner(0, 0, 1, w), corner(1, 0, 1, w), u.x),
+                    lerp(corner(0, 1, 1, w), corner(1, 1, 1, w), u.x),
+                    u.y,
+                ),
+                u.z,
+            )
+        };
+
+        lerp(cube(0), cube(1), u.w)
+    }
+}
 
 pub struct Tileable3dNoise;
 impl Tileable3dNoise {
+    // Seed 0 maps to an offset of 0, reproducing the unseeded noise exactly.
+    fn hash_offset(seed: u32) -> f32 {
+        ((seed as f64 * 0.618_033_988_749_895).fract() * 1000.0) as f32
+    }
+
     fn hash(n: f32) -> f32 {
         let x = (n + 1.951f32).sin() * 43758.547f32;
         // Original implementation uses `glm::fract`, which is implemented as `x - floor(x)`
@@ -11,12 +473,12 @@ impl Tileable3dNoise {
         x - x.floor()
     }
 
-    fn noise(x: Vec3) -> f32 {
+    fn noise(x: Vec3, hash_offset: f32) -> f32 {
         let p = x.floor();
         let f = x - x.floor();
 
         let f = f * f * (Vec3::splat(3.0) - Vec3::splat(2.0) * f);
-        let n = p.x + p.y * 57.0f32 + 113.0f32 * p.z;
+        let n = p.x + p.y * 57.0f32 + 113.0f32 * p.z + hash_offset;
 
         lerp(
             lerp(
@@ -33,7 +495,7 @@ impl Tileable3dNoise {
         )
     }
 
-    fn cells(p: Vec3, cell_count: f32) -> f32 {
+    fn cells(p: Vec3, cell_count: f32, hash_offset: f32) -> f32 {
         let p_cell = p * cell_count;
         let mut d = 1.0e10f32;
 
@@ -41,7 +503,9 @@ impl Tileable3dNoise {
             for y in -1..=1 {
                 for z in -1..=1 {
                     let tp = p_cell.floor() + Vec3::new(x as f32, y as f32, z as f32);
-                    let tp = p_cell - tp - Self::noise(glm_mod_3(tp, Vec3::splat(cell_count)));
+                    let tp = p_cell
+                        - tp
+                        - Self::noise(glm_mod_3(tp, Vec3::splat(cell_count)), hash_offset);
 
                     d = d.min(tp.dot(tp));
                 }
@@ -51,11 +515,75 @@ impl Tileable3dNoise {
         d.clamp(0.0, 1.0)
     }
 
+    // `x` modulo `period`, as `glm::mod`
+    fn wrap(x: f32, period: f32) -> f32 {
+        x - period * (x / period).floor()
+    }
+
+    fn cells_4d(p: Vec3, time: f32, cell_count: f32, hash_offset: f32) -> f32 {
+        let p_cell = p * cell_count;
+        let time_cell = time * cell_count;
+        let mut d = 1.0e10f32;
+
+        for w in TIME_NEIGHBORS {
+            let tw = time_cell.floor() + w as f32;
+            let layer_hash_offset = hash_offset + TIME_CELL_HASH_STEP * Self::wrap(tw, cell_count);
+            for x in -1..=1 {
+                for y in -1..=1 {
+                    for z in -1..=1 {
+                        let tp = p_cell.floor() + Vec3::new(x as f32, y as f32, z as f32);
+                        let offset =
+                            Self::noise(glm_mod_3(tp, Vec3::splat(cell_count)), layer_hash_offset);
+                        let tp = p_cell - tp - offset;
+                        let tw = time_cell - tw - offset;
+
+                        d = d.min(tp.dot(tp) + tw * tw);
+                    }
+                }
+            }
+        }
+
+        d.clamp(0.0, 1.0)
+    }
+
     pub fn worley_noise(p: Vec3, cell_count: f32) -> f32 {
-        Self::cells(p, cell_count)
+        Self::worley_noise_seeded(p, cell_count, 0)
     }
 
-    pub fn perlin_noise(p: Vec3, mut frequency: f32, octave_count: u32) -> f32 {
+    /// Same as [`Self::worley_noise`], with the feature points placed according to `seed`.
+    pub fn worley_noise_seeded(p: Vec3, cell_count: f32, seed: u32) -> f32 {
+        Self::cells(p, cell_count, Self::hash_offset(seed))
+    }
+
+    /// Same as [`Self::worley_noise_seeded`], with `time` as 4th coordinate, also split in
+    /// `cell_count` cells. The noise repeats every 1.0 in time as in space, so `time` from 0.0 to
+    /// 1.0 animates it in a seamless loop.
+    pub fn worley_noise_4d(p: Vec3, time: f32, cell_count: f32, seed: u32) -> f32 {
+        Self::cells_4d(p, time, cell_count, Self::hash_offset(seed))
+    }
+
+    pub fn perlin_noise(p: Vec3, frequency: f32, octave_count: u32) -> f32 {
+        Self::perlin_noise_seeded(p, frequency, octave_count, 0)
+    }
+
+    /// Same as [`Self::perlin_noise`], with the gradients permuted according to `seed`.
+    ///
+    /// Perlin noise repeats every 289 seeds.
+    pub fn perlin_noise_seeded(p: Vec3, frequency: f32, octave_count: u32, seed: u32) -> f32 {
+        Self::perlin_noise_4d(p, 0.0, frequency, octave_count, seed)
+    }
+
+    /// Same as [`Self::perlin_noise_seeded`], with `time` as 4th coordinate. The noise repeats
+    /// every 1.0 in time as in space, so `time` from 0.0 to 1.0 animates it in a seamless loop.
+    ///
+    /// Time 0.0 gives the same noise as [`Self::perlin_noise_seeded`].
+    pub fn perlin_noise_4d(
+        p: Vec3,
+        time: f32,
+        mut frequency: f32,
+        octave_count: u32,
+        seed: u32,
+    ) -> f32 {
         let octaves_freq_factor = 2.0; // noise frequency factor between octave, forced to 2
 
         // Compute the sum for each octave
@@ -63,12 +591,12 @@ impl Tileable3dNoise {
         let mut weight_sum = 0.0;
         let mut weight = 0.5;
 
-        // TODO: Consider implementing `glm_perlin` to remove dependency on noise crate?
         for _ in 0..octave_count {
             let point = p * frequency;
             let val = glm_perlin_vec4(
-                Vec4::new(point.x, point.y, point.z, 0.0),
+                Vec4::new(point.x, point.y, point.z, time * frequency),
                 Vec4::splat(frequency),
+                (seed % 289) as f32,
             );
 
             sum += val * weight;
@@ -81,4 +609,67 @@ impl Tileable3dNoise {
         let noise = (sum / weight_sum) * 0.5 + 0.5;
         noise.clamp(0.0, 1.0)
     }
+
+    /// [`Self::perlin_noise_seeded`] at four points at once, using SIMD lanes.
+    pub fn perlin_noise_x4(p: [Vec3; 4], frequency: f32, octave_count: u32, seed: u32) -> [f32; 4] {
+        Self::perlin_noise_lanes::<Vec4, 4>(p, 0.0, frequency, octave_count, seed)
+    }
+
+    /// [`Self::perlin_noise_seeded`] at eight points at once, using SIMD lanes.
+    pub fn perlin_noise_x8(p: [Vec3; 8], frequency: f32, octave_count: u32, seed: u32) -> [f32; 8] {
+        Self::perlin_noise_lanes::<Vec4x2, 8>(p, 0.0, frequency, octave_count, seed)
+    }
+
+    // `perlin_noise_4d` at eight points at once, using SIMD lanes
+    pub(crate) fn perlin_noise_4d_x8(
+        p: [Vec3; 8],
+        time: f32,
+        frequency: f32,
+        octave_count: u32,
+        seed: u32,
+    ) -> [f32; 8] {
+        Self::perlin_noise_lanes::<Vec4x2, 8>(p, time, frequency, octave_count, seed)
+    }
+
+    fn perlin_noise_lanes<L: Lanes, const N: usize>(
+        p: [Vec3; N],
+        time: f32,
+        mut frequency: f32,
+        octave_count: u32,
+        seed: u32,
+    ) -> [f32; N] {
+        debug_assert_eq!(L::COUNT, N);
+        let p = [
+            L::from_slice(&p.map(|p| p.x)),
+            L::from_slice(&p.map(|p| p.y)),
+            L::from_slice(&p.map(|p| p.z)),
+        ];
+
+        let mut sum = L::splat(0.0);
+        let mut weight_sum = 0.0;
+        let mut weight = 0.5;
+
+        for _ in 0..octave_count {
+            let point = p.map(|p| p * L::splat(frequency));
+            let val = glm_perlin_lanes(
+                [point[0], point[1], point[2], L::splat(time * frequency)],
+                [L::splat(frequency); 4],
+                (seed % 289) as f32,
+            );
+
+            sum = sum + val * L::splat(weight);
+            weight_sum += weight;
+
+            weight *= weight;
+            frequency *= 2.0;
+        }
+
+        let noise = (sum / L::splat(weight_sum)) * L::splat(0.5) + L::splat(0.5);
+        let mut result = [0.0; N];
+        noise
+            .max(L::splat(0.0))
+            .min(L::splat(1.0))
+            .write_to_slice(&mut result);
+        result
+    }
 }
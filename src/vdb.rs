@@ -0,0 +1,298 @@
+use std::{
+    collections::hash_map::RandomState,
+    fs,
+    hash::{BuildHasher, Hasher},
+    io,
+    path::Path,
+};
+
+use crate::TileableCloudNoise;
+
+// Dense OpenVDB writer for the standard `FloatGrid` tree configuration: a root node with a single
+// 4096^3 internal node, 128^3 internal nodes below that and 8^3 leaves. All voxels inside the
+// volume are active, everything else is inactive background.
+//
+// The layout follows `openvdb/io/Archive.cc` for file format version 224, uncompressed.
+
+const FILE_VERSION: u32 = 224;
+const LIBRARY_VERSION: (u32, u32) = (9, 0);
+// Per-buffer metadata flag: every value is stored, no selection mask follows.
+const NO_MASK_AND_ALL_VALS: u8 = 6;
+
+const UPPER_LOG2_DIM: u32 = 5;
+const LOWER_LOG2_DIM: u32 = 4;
+const LEAF_LOG2_DIM: u32 = 3;
+const LEAF_DIM: u32 = 1 << LEAF_LOG2_DIM;
+const LOWER_DIM: u32 = LEAF_DIM << LOWER_LOG2_DIM;
+const UPPER_DIM: u32 = LOWER_DIM << UPPER_LOG2_DIM;
+
+#[derive(Default)]
+struct VdbBuffer(Vec<u8>);
+
+impl VdbBuffer {
+    fn position(&self) -> i64 {
+        self.0.len() as i64
+    }
+
+    fn bytes(&mut self, bytes: &[u8]) {
+        self.0.extend_from_slice(bytes);
+    }
+
+    fn u32(&mut self, value: u32) {
+        self.bytes(&value.to_le_bytes());
+    }
+
+    fn i64(&mut self, value: i64) {
+        self.bytes(&value.to_le_bytes());
+    }
+
+    fn f32(&mut self, value: f32) {
+        self.bytes(&value.to_le_bytes());
+    }
+
+    fn vec3d(&mut self, value: f64) {
+        for _ in 0..3 {
+            self.bytes(&value.to_le_bytes());
+        }
+    }
+
+    fn string(&mut self, value: &str) {
+        self.u32(value.len() as u32);
+        self.bytes(value.as_bytes());
+    }
+
+    fn mask(&mut self, mask: &[u64]) {
+        for word in mask {
+            self.bytes(&word.to_le_bytes());
+        }
+    }
+
+    fn values(&mut self, values: &[f32]) {
+        self.bytes(&[NO_MASK_AND_ALL_VALS]);
+        for &value in values {
+            self.f32(value);
+        }
+    }
+}
+
+fn set_bit(mask: &mut [u64], n: u32) {
+    mask[(n >> 6) as usize] |= 1 << (n & 63);
+}
+
+// Origins of the children of a node at `origin` with child size `child_dim` that overlap the
+// `0..resolution` volume, in the order of their linear index within the node.
+fn child_origins(
+    origin: [u32; 3],
+    node_dim: u32,
+    child_dim: u32,
+    resolution: u32,
+) -> Vec<[u32; 3]> {
+    let count = |o: u32| (resolution.min(o + node_dim) - o).div_ceil(child_dim);
+
+    let mut origins = Vec::new();
+    for x in 0..count(origin[0]) {
+        for y in 0..count(origin[1]) {
+            for z in 0..count(origin[2]) {
+                origins.push([
+                    origin[0] + x * child_dim,
+                    origin[1] + y * child_dim,
+                    origin[2] + z * child_dim,
+                ]);
+            }
+        }
+    }
+    origins
+}
+
+fn child_offset(origin: [u32; 3], node_origin: [u32; 3], child_dim: u32, log2_dim: u32) -> u32 {
+    let [x, y, z] = [0, 1, 2].map(|i| (origin[i] - node_origin[i]) / child_dim);
+    (x << (2 * log2_dim)) | (y << log2_dim) | z
+}
+
+fn internal_node_topology(
+    buffer: &mut VdbBuffer,
+    node_origin: [u32; 3],
+    children: &[[u32; 3]],
+    child_dim: u32,
+    log2_dim: u32,
+) {
+    let num_values = 1usize << (3 * log2_dim);
+    let mut child_mask = vec![0u64; num_values / 64];
+    for &child in children {
+        set_bit(
+            &mut child_mask,
+            child_offset(child, node_origin, child_dim, log2_dim),
+        );
+    }
+
+    buffer.mask(&child_mask);
+    // No active tiles
+    buffer.mask(&vec![0u64; num_values / 64]);
+    buffer.values(&vec![0.0; num_values]);
+}
+
+fn uuid() -> String {
+    let mut hasher = RandomState::new().build_hasher();
+    hasher.write_u32(0);
+    let high = hasher.finish();
+    hasher.write_u32(1);
+    let low = hasher.finish();
+
+    // Random (version 4) UUID
+    let high = (high & !0xf000) | 0x4000;
+    let low = (low & !(0xc << 60)) | (0x8 << 60);
+    format!(
+        "{:08x}-{:04x}-{:04x}-{:04x}-{:012x}",
+        high >> 32,
+        (high >> 16) & 0xffff,
+        high & 0xffff,
+        low >> 48,
+        low & 0xffff_ffff_ffff
+    )
+}
+
+impl TileableCloudNoise {
+    /// Writes `channel` as a dense OpenVDB float grid, named after the channel, that can be
+    /// loaded in Houdini, Blender and other DCC tools.
+    ///
+    /// Every texel becomes an active voxel of `voxel_size` world units, with the volume starting
+    /// at the world origin. Integer formats are written normalized to `0..=1`.
+    ///
+    /// Returns an error of kind [`io::ErrorKind::InvalidInput`] if `channel` is out of range or
+    /// the resolution is above 4096.
+    pub fn write_vdb(
+        &self,
+        path: impl AsRef<Path>,
+        channel: u32,
+        voxel_size: f64,
+    ) -> io::Result<()> {
+        if channel >= self.num_channels {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("channel {channel} out of range"),
+            ));
+        }
+        if self.resolution > UPPER_DIM {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!(
+                    "resolution {} exceeds the single root child supported by the writer",
+                    self.resolution
+                ),
+            ));
+        }
+
+        let res = self.resolution;
+        let mut buffer = VdbBuffer::default();
+
+        // Header
+        buffer.bytes(&0x5644_4220i64.to_le_bytes());
+        buffer.u32(FILE_VERSION);
+        buffer.u32(LIBRARY_VERSION.0);
+        buffer.u32(LIBRARY_VERSION.1);
+        // Has grid offsets
+        buffer.bytes(&[1]);
+        buffer.bytes(uuid().as_bytes());
+        // File-level metadata
+        buffer.u32(0);
+        // Grid count
+        buffer.u32(1);
+
+        // Grid descriptor
+        buffer.string(&self.channel_names[channel as usize]);
+        buffer.string("Tree_float_5_4_3");
+        // Instance parent
+        buffer.string("");
+        let offsets_pos = buffer.position() as usize;
+        buffer.i64(0);
+        buffer.i64(0);
+        buffer.i64(0);
+        let grid_pos = buffer.position();
+
+        // Compression flags
+        buffer.u32(0);
+
+        // Grid metadata
+        buffer.u32(1);
+        buffer.string("class");
+        buffer.string("string");
+        buffer.string("fog volume");
+
+        // Transform
+        buffer.string("UniformScaleMap");
+        buffer.vec3d(voxel_size);
+        buffer.vec3d(voxel_size);
+        buffer.vec3d(1.0 / voxel_size);
+        buffer.vec3d(1.0 / (voxel_size * voxel_size));
+        buffer.vec3d(0.5 / voxel_size);
+
+        // Topology
+        let upper_origin = [0; 3];
+        let lower_origins = child_origins(upper_origin, UPPER_DIM, LOWER_DIM, res);
+        let leaf_origins = lower_origins
+            .iter()
+            .map(|&origin| child_origins(origin, LOWER_DIM, LEAF_DIM, res))
+            .collect::<Vec<_>>();
+
+        // Buffer count
+        buffer.u32(1);
+        // Root node: background, tile count, child count
+        buffer.f32(0.0);
+        buffer.u32(0);
+        buffer.u32(1);
+        for coord in upper_origin {
+            buffer.u32(coord);
+        }
+        internal_node_topology(
+            &mut buffer,
+            upper_origin,
+            &lower_origins,
+            LOWER_DIM,
+            UPPER_LOG2_DIM,
+        );
+
+        let leaf_value_mask = |origin: [u32; 3]| {
+            let mut mask = [0u64; 8];
+            for x in 0..LEAF_DIM.min(res - origin[0]) {
+                for y in 0..LEAF_DIM.min(res - origin[1]) {
+                    for z in 0..LEAF_DIM.min(res - origin[2]) {
+                        set_bit(&mut mask, (x << 6) | (y << 3) | z);
+                    }
+                }
+            }
+            mask
+        };
+
+        for (&lower_origin, leaves) in lower_origins.iter().zip(&leaf_origins) {
+            internal_node_topology(&mut buffer, lower_origin, leaves, LEAF_DIM, LOWER_LOG2_DIM);
+            for &leaf in leaves {
+                buffer.mask(&leaf_value_mask(leaf));
+            }
+        }
+
+        // Leaf buffers
+        let block_pos = buffer.position();
+        for &leaf in leaf_origins.iter().flatten() {
+            let mut values = [0.0f32; 512];
+            for x in 0..LEAF_DIM.min(res - leaf[0]) {
+                for y in 0..LEAF_DIM.min(res - leaf[1]) {
+                    for z in 0..LEAF_DIM.min(res - leaf[2]) {
+                        values[((x << 6) | (y << 3) | z) as usize] =
+                            self.sample(leaf[0] + x, leaf[1] + y, leaf[2] + z, channel);
+                    }
+                }
+            }
+
+            buffer.mask(&leaf_value_mask(leaf));
+            buffer.values(&values);
+        }
+        let end_pos = buffer.position();
+
+        for (i, pos) in [grid_pos, block_pos, end_pos].into_iter().enumerate() {
+            let offset = offsets_pos + i * 8;
+            buffer.0[offset..offset + 8].copy_from_slice(&pos.to_le_bytes());
+        }
+
+        fs::write(path, buffer.0)
+    }
+}
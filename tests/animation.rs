@@ -0,0 +1,76 @@
+mod common;
+
+use common::{assert_close, points, TOLERANCE};
+use tileable_volume_noise::{
+    CloudPreset, GenerationDesc, PerlinBackend, PerlinTable, TexelFormat, Tileable3dNoise,
+    TileableCloudNoise, WorleyGrid,
+};
+
+#[test]
+fn worley_4d_grids_match_scalar() {
+    let points = points();
+    for (cell_count, seed, time) in [(4.0, 0, 0.0), (8.0, 2, 0.3), (13.0, 0, 0.99)] {
+        let grid = WorleyGrid::at_time(cell_count, seed, time).unwrap();
+        let scalar = |p| Tileable3dNoise::worley_noise_4d(p, time, cell_count, seed);
+
+        assert_close(
+            &points
+                .iter()
+                .map(|&p| grid.worley_noise(p))
+                .collect::<Vec<_>>(),
+            scalar,
+            &points,
+        );
+        for chunk in points.chunks_exact(8) {
+            assert_close(
+                &grid.worley_noise_x8(chunk.try_into().unwrap()),
+                scalar,
+                chunk,
+            );
+        }
+    }
+}
+
+#[test]
+fn noise_loops_in_time() {
+    let table = PerlinTable::new(5);
+    for p in points() {
+        let perlin = |time| Tileable3dNoise::perlin_noise_4d(p, time, 4.0, 3, 0);
+        assert!((perlin(0.0) - perlin(1.0)).abs() <= TOLERANCE);
+        assert_eq!(
+            perlin(0.0),
+            Tileable3dNoise::perlin_noise_seeded(p, 4.0, 3, 0)
+        );
+
+        let table_perlin = |time| table.perlin_noise_4d(p, time, 4.0, 3);
+        assert!((table_perlin(0.0) - table_perlin(1.0)).abs() <= TOLERANCE);
+        assert!((0.0..=1.0).contains(&table_perlin(0.4)));
+
+        let worley = |time| Tileable3dNoise::worley_noise_4d(p, time, 6.0, 0);
+        assert!((worley(0.0) - worley(1.0)).abs() <= TOLERANCE);
+    }
+}
+
+#[test]
+fn recipes_loop_in_time() {
+    let recipe = CloudPreset::Schneider2015.shape_recipe();
+    for perlin_backend in [PerlinBackend::Glm, PerlinBackend::Table] {
+        let bake = |time| {
+            TileableCloudNoise::from_recipe(
+                &recipe,
+                &GenerationDesc {
+                    format: TexelFormat::Float32,
+                    perlin_backend,
+                    time: Some(time),
+                    ..GenerationDesc::new(16)
+                },
+            )
+            .unwrap()
+            .data
+        };
+
+        let start = bake(0.0);
+        assert_eq!(start, bake(1.0), "{perlin_backend:?}");
+        assert_ne!(start, bake(0.5), "{perlin_backend:?} does not move");
+    }
+}
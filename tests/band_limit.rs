@@ -0,0 +1,146 @@
+use tileable_volume_noise::{BandLimit, CloudPreset, NoiseNode, Recipe, RecipeChannel};
+
+fn limit(node: NoiseNode, resolution: u32, band_limit: BandLimit) -> NoiseNode {
+    let recipe = Recipe {
+        channels: vec![RecipeChannel {
+            name: "noise".to_string(),
+            node,
+        }],
+    };
+    recipe
+        .band_limited(resolution, band_limit)
+        .channels
+        .remove(0)
+        .node
+}
+
+// Collects the `from_min` bounds of every remap under `node`
+fn remap_bounds<'a>(node: &'a NoiseNode, bounds: &mut Vec<&'a NoiseNode>) {
+    match node {
+        NoiseNode::Constant(_)
+        | NoiseNode::Perlin { .. }
+        | NoiseNode::Worley { .. }
+        | NoiseNode::WorleyFbm { .. } => {}
+        NoiseNode::Invert(input) => remap_bounds(input, bounds),
+        NoiseNode::Remap {
+            input,
+            from_min,
+            from_max,
+            to_min,
+            to_max,
+        } => {
+            bounds.push(from_min);
+            for node in [input, from_min, from_max, to_min, to_max] {
+                remap_bounds(node, bounds);
+            }
+        }
+        NoiseNode::WeightedSum(nodes) => {
+            for node in nodes {
+                remap_bounds(&node.node, bounds);
+            }
+        }
+    }
+}
+
+#[test]
+fn band_limiting_keeps_dilation_offsets() {
+    for preset in [CloudPreset::Schneider2015, CloudPreset::Nubis2017] {
+        for resolution in [128, 48, 24, 16] {
+            let recipe = preset
+                .shape_recipe()
+                .band_limited(resolution, BandLimit::Fade);
+            let mut bounds = Vec::new();
+            for channel in &recipe.channels {
+                remap_bounds(&channel.node, &mut bounds);
+            }
+
+            let dilations = bounds
+                .iter()
+                .filter_map(|bound| match bound {
+                    NoiseNode::WeightedSum(nodes) => Some(nodes),
+                    _ => None,
+                })
+                .collect::<Vec<_>>();
+            assert!(!dilations.is_empty(), "{preset:?} has no dilated channel");
+            for nodes in dilations {
+                assert!(
+                    nodes
+                        .iter()
+                        .any(|node| !matches!(node.node, NoiseNode::Constant(_))),
+                    "{preset:?} at {resolution} lost the noise of a dilation: {nodes:?}"
+                );
+                assert!(
+                    nodes
+                        .iter()
+                        .any(|node| node.node == NoiseNode::Constant(1.0) && node.weight == -1.0),
+                    "{preset:?} at {resolution} lost the offset of a dilation: {nodes:?}"
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn worley_fbm_octaves_are_limited() {
+    // Octaves at 4, 8 and 16 cells
+    let fbm = NoiseNode::WorleyFbm {
+        cell_count: 4.0,
+        weights: vec![0.5, 0.3, 0.2],
+    };
+    let weights = |resolution, band_limit| match limit(fbm.clone(), resolution, band_limit) {
+        NoiseNode::WorleyFbm { weights, .. } => weights,
+        node => panic!("{node:?}"),
+    };
+
+    assert_eq!(weights(32, BandLimit::Off), [0.5, 0.3, 0.2]);
+    assert_eq!(weights(16, BandLimit::Off), [0.5, 0.3, 0.2]);
+    // Nothing above the Nyquist frequency of 16 cells
+    assert_eq!(weights(32, BandLimit::Drop), [0.5, 0.3, 0.2]);
+    // 16 cells are above 8, the remaining weights keep their sum
+    assert_eq!(weights(16, BandLimit::Drop), [0.625, 0.375]);
+
+    // 8 cells fade out between 6 and 12, 16 cells are dropped
+    let faded = weights(24, BandLimit::Fade);
+    assert_eq!(faded.len(), 2);
+    assert!(faded[1] / faded[0] < 0.3 / 0.5, "{faded:?}");
+    assert!((faded.iter().sum::<f32>() - 1.0).abs() <= 1e-6, "{faded:?}");
+}
+
+#[test]
+fn perlin_octaves_are_limited() {
+    // Octaves at 4, 8, 16 and 32 cycles
+    let perlin = NoiseNode::Perlin {
+        frequency: 4.0,
+        octave_count: 4,
+    };
+
+    assert_eq!(limit(perlin.clone(), 64, BandLimit::Drop), perlin);
+    // Dropping whole octaves keeps the weights of the others
+    assert_eq!(
+        limit(perlin.clone(), 32, BandLimit::Drop),
+        NoiseNode::Perlin {
+            frequency: 4.0,
+            octave_count: 3,
+        }
+    );
+
+    // Fading rewrites the octaves as a sum of single octaves
+    let NoiseNode::WeightedSum(octaves) = limit(perlin, 24, BandLimit::Fade) else {
+        panic!("faded Perlin noise is not a weighted sum");
+    };
+    let frequencies = octaves
+        .iter()
+        .map(|octave| match octave.node {
+            NoiseNode::Perlin {
+                frequency,
+                octave_count: 1,
+            } => frequency,
+            ref node => panic!("{node:?}"),
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(frequencies, [4.0, 8.0]);
+    // The second octave weighs half the first without band limiting
+    assert!(octaves[1].weight / octaves[0].weight < 0.5, "{octaves:?}");
+    let sum = octaves.iter().map(|octave| octave.weight).sum::<f32>();
+    assert!((sum - 1.0).abs() <= 1e-6, "{octaves:?}");
+}
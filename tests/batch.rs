@@ -0,0 +1,70 @@
+mod common;
+
+use common::{assert_close, points};
+use tileable_volume_noise::{Tileable3dNoise, WorleyGrid};
+
+#[test]
+fn perlin_batches_match_scalar() {
+    let points = points();
+    for (frequency, octave_count, seed) in [(8.0, 3, 0), (4.0, 1, 7), (3.0, 4, 300)] {
+        let scalar = |p| Tileable3dNoise::perlin_noise_seeded(p, frequency, octave_count, seed);
+
+        for chunk in points.chunks_exact(4) {
+            let batch = Tileable3dNoise::perlin_noise_x4(
+                chunk.try_into().unwrap(),
+                frequency,
+                octave_count,
+                seed,
+            );
+            assert_close(&batch, scalar, chunk);
+        }
+        for chunk in points.chunks_exact(8) {
+            let batch = Tileable3dNoise::perlin_noise_x8(
+                chunk.try_into().unwrap(),
+                frequency,
+                octave_count,
+                seed,
+            );
+            assert_close(&batch, scalar, chunk);
+        }
+    }
+}
+
+#[test]
+fn worley_batches_match_scalar() {
+    let points = points();
+    for (cell_count, seed) in [(1.0, 0), (8.0, 0), (13.0, 5), (56.0, 1)] {
+        let grid = WorleyGrid::new(cell_count, seed).unwrap();
+        let scalar = |p| Tileable3dNoise::worley_noise_seeded(p, cell_count, seed);
+
+        assert_close(
+            &points
+                .iter()
+                .map(|&p| grid.worley_noise(p))
+                .collect::<Vec<_>>(),
+            scalar,
+            &points,
+        );
+        for chunk in points.chunks_exact(4) {
+            assert_close(
+                &grid.worley_noise_x4(chunk.try_into().unwrap()),
+                scalar,
+                chunk,
+            );
+        }
+        for chunk in points.chunks_exact(8) {
+            assert_close(
+                &grid.worley_noise_x8(chunk.try_into().unwrap()),
+                scalar,
+                chunk,
+            );
+        }
+    }
+}
+
+#[test]
+fn worley_grid_rejects_fractional_cell_counts() {
+    assert!(WorleyGrid::new(2.5, 0).is_none());
+    assert!(WorleyGrid::new(0.0, 0).is_none());
+    assert!(WorleyGrid::new(512.0, 0).is_none());
+}
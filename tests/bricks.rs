@@ -0,0 +1,88 @@
+use glam::Vec3;
+use tileable_volume_noise::{
+    BrickedVolume, GenerationDesc, TexelFormat, Tileable3dNoise, TileableCloudNoise,
+};
+
+fn volume(texel: impl Fn(Vec3) -> f32 + Sync) -> TileableCloudNoise {
+    let desc = GenerationDesc {
+        format: TexelFormat::Float32,
+        ..GenerationDesc::new(16)
+    };
+    TileableCloudNoise::from_fn(&desc, ["value"], |uvw| [texel(uvw)]).unwrap()
+}
+
+// Checks every texel of every brick, borders included, against the wrapped texel of the volume
+fn assert_bricks_match(volume: &TileableCloudNoise, bricks: &BrickedVolume) {
+    let res = volume.resolution as i32;
+    let padded_size = bricks.padded_size() as i32;
+    for position in (0..bricks.bricks_per_axis.pow(3)).map(|i| {
+        let count = bricks.bricks_per_axis;
+        [i % count, (i / count) % count, i / (count * count)]
+    }) {
+        let brick = bricks.brick(position);
+        for z in 0..padded_size {
+            for y in 0..padded_size {
+                for x in 0..padded_size {
+                    let [vx, vy, vz] = [(x, 0), (y, 1), (z, 2)].map(|(i, axis)| {
+                        (position[axis] as i32 * bricks.brick_size as i32 + i
+                            - bricks.border as i32)
+                            .rem_euclid(res) as u32
+                    });
+                    let offset = (((z * padded_size + y) * padded_size + x) * 4) as usize;
+                    let value = f32::from_le_bytes(brick[offset..offset + 4].try_into().unwrap());
+                    assert_eq!(
+                        value,
+                        volume.sample(vx, vy, vz, 0),
+                        "texel {x} {y} {z} of brick {position:?}"
+                    );
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn brick_borders_wrap() {
+    let volume = volume(|uvw| Tileable3dNoise::worley_noise(uvw, 3.0));
+    let bricks = BrickedVolume::new(&volume, 8, 2).unwrap();
+    assert_eq!(bricks.bricks_per_axis, 2);
+    assert_eq!(bricks.stored_brick_count(), 8);
+    assert_bricks_match(&volume, &bricks);
+
+    // The border before the first brick is the last texels of the volume
+    let padded_size = bricks.padded_size() as usize;
+    let first = bricks.brick([0, 0, 0]);
+    let last = volume.resolution - 1;
+    for (padded, volume_coord) in [(0, last - 1), (1, last)] {
+        let offset = ((padded * padded_size + padded) * padded_size + padded) * 4;
+        let value = f32::from_le_bytes(first[offset..offset + 4].try_into().unwrap());
+        assert_eq!(
+            value,
+            volume.sample(volume_coord, volume_coord, volume_coord, 0)
+        );
+    }
+}
+
+#[test]
+fn identical_bricks_are_stored_once() {
+    // Repeats every half of the volume along x, so bricks next to each other along x are the same
+    let volume =
+        volume(|uvw| Tileable3dNoise::worley_noise(Vec3::new(uvw.x * 2.0, uvw.y, uvw.z), 2.0));
+    let bricks = BrickedVolume::new(&volume, 8, 1).unwrap();
+    assert_eq!(bricks.stored_brick_count(), 4);
+    for pair in bricks.index.chunks_exact(2) {
+        assert_eq!(pair[0], pair[1]);
+    }
+    assert_bricks_match(&volume, &bricks);
+
+    let constant = BrickedVolume::new(&self::volume(|_| 0.5), 4, 2).unwrap();
+    assert_eq!(constant.stored_brick_count(), 1);
+    assert_eq!(constant.index.len(), 4 * 4 * 4);
+}
+
+#[test]
+fn bricks_divide_the_resolution() {
+    let volume = volume(|_| 0.0);
+    assert!(BrickedVolume::new(&volume, 0, 1).is_none());
+    assert!(BrickedVolume::new(&volume, 5, 1).is_none());
+}
@@ -0,0 +1,84 @@
+#![cfg(feature = "cli")]
+
+use std::{
+    env, fs,
+    path::Path,
+    process::{Command, Output},
+};
+
+use tileable_volume_noise::Recipe;
+
+fn run(args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_tileable-volume-noise"))
+        .args(args)
+        .output()
+        .unwrap()
+}
+
+#[test]
+fn unknown_extensions_are_rejected_before_baking() {
+    let path = env::temp_dir().join("tileable_volume_noise_cli.tga");
+    let output = run(&["shape", "-r", "4", "-o", path.to_str().unwrap()]);
+
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("output must end in"), "{stderr}");
+    assert!(output.stdout.is_empty());
+    assert!(!path.exists());
+}
+
+#[test]
+fn presets_conflict_with_perlin_worley() {
+    let path = env::temp_dir().join("tileable_volume_noise_cli_conflict.raw");
+    let output = run(&[
+        "shape",
+        "--preset",
+        "schneider2015",
+        "--perlin-worley",
+        "figure4-7",
+        "-o",
+        path.to_str().unwrap(),
+    ]);
+
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("cannot be used with"), "{stderr}");
+    assert!(!path.exists());
+}
+
+#[test]
+fn recipe_matches_built_in_texture() {
+    let dir = env::temp_dir();
+    let recipe_path = dir.join("tileable_volume_noise_cli_recipe.json");
+    let recipe_output = dir.join("tileable_volume_noise_cli_from_recipe.raw");
+    let built_in_output = dir.join("tileable_volume_noise_cli_shape.raw");
+    fs::write(
+        &recipe_path,
+        serde_json::to_string(&Recipe::cloud_shape_and_erosion()).unwrap(),
+    )
+    .unwrap();
+
+    let from_recipe = run(&[
+        "--recipe",
+        recipe_path.to_str().unwrap(),
+        "-r",
+        "32",
+        "-o",
+        recipe_output.to_str().unwrap(),
+    ]);
+    let built_in = run(&["shape", "-r", "32", "-o", built_in_output.to_str().unwrap()]);
+    let read = |path: &Path| {
+        let data = fs::read(path);
+        fs::remove_file(path).unwrap();
+        fs::remove_file(path.with_extension("json")).unwrap();
+        data.unwrap()
+    };
+    assert!(from_recipe.status.success(), "{from_recipe:?}");
+    assert!(built_in.status.success(), "{built_in:?}");
+    let recipe_data = read(&recipe_output);
+    let built_in_data = read(&built_in_output);
+    fs::remove_file(&recipe_path).unwrap();
+
+    assert_eq!(recipe_data.len(), 32 * 32 * 32 * 4);
+    assert!(recipe_data == built_in_data);
+}
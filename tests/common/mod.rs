@@ -0,0 +1,46 @@
+// Helpers shared by the integration tests, each of which uses a different subset of them
+#![allow(dead_code)]
+
+use glam::Vec3;
+use tileable_volume_noise::TileableCloudNoise;
+
+pub const TOLERANCE: f32 = 1e-6;
+
+// Points spread over and slightly beyond the unit cube, including the integer boundaries
+pub fn points() -> Vec<Vec3> {
+    let mut state = 0x2545_f491u32;
+    let mut random = move || {
+        state ^= state << 13;
+        state ^= state >> 17;
+        state ^= state << 5;
+        state as f32 / u32::MAX as f32 * 1.5 - 0.25
+    };
+
+    let mut points = vec![
+        Vec3::ZERO,
+        Vec3::ONE,
+        Vec3::splat(0.5),
+        Vec3::new(1.0, 0.0, 0.25),
+    ];
+    points.extend((0..252).map(|_| Vec3::new(random(), random(), random())));
+    points
+}
+
+pub fn assert_close(batch: &[f32], scalar: impl Fn(Vec3) -> f32, points: &[Vec3]) {
+    for (&value, &p) in batch.iter().zip(points) {
+        let expected = scalar(p);
+        assert!(
+            (value - expected).abs() <= TOLERANCE,
+            "{value} != {expected} at {p}"
+        );
+    }
+}
+
+// Checks that a volume read back from a file is the volume that was written
+pub fn assert_same(read: &TileableCloudNoise, noise: &TileableCloudNoise) {
+    assert_eq!(read.resolution, noise.resolution);
+    assert_eq!(read.num_channels, noise.num_channels);
+    assert_eq!(read.bytes_per_channel, noise.bytes_per_channel);
+    assert_eq!(read.channel_names, noise.channel_names);
+    assert!(read.data == noise.data);
+}
@@ -0,0 +1,61 @@
+use std::sync::{
+    atomic::{AtomicU32, Ordering},
+    Arc, Mutex,
+};
+
+use tileable_volume_noise::{
+    Cancelled, GenerationControl, GenerationDesc, Recipe, Threads, TileableCloudNoise,
+};
+
+#[test]
+fn progress_is_reported_once_per_slice() {
+    let reports = Arc::new(Mutex::new(Vec::new()));
+    let control = GenerationControl::new().with_progress({
+        let reports = reports.clone();
+        move |completed, total| reports.lock().unwrap().push((completed, total))
+    });
+    let desc = GenerationDesc {
+        control,
+        ..GenerationDesc::new(12)
+    };
+    TileableCloudNoise::from_recipe(&Recipe::details(), &desc).unwrap();
+
+    let mut reports = reports.lock().unwrap().clone();
+    reports.sort();
+    assert_eq!(
+        reports,
+        (1..=12)
+            .map(|completed| (completed, 12))
+            .collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn cancelling_stops_the_bake() {
+    let calls = Arc::new(AtomicU32::new(0));
+    let control = GenerationControl::new();
+    let desc = GenerationDesc {
+        control: control.clone().with_progress({
+            let calls = calls.clone();
+            move |completed, _| {
+                calls.fetch_add(1, Ordering::Relaxed);
+                if completed == 3 {
+                    control.cancel();
+                }
+            }
+        }),
+        // One thread, so that no other slice is in flight when the third one completes
+        threads: Threads::Count(1),
+        ..GenerationDesc::new(16)
+    };
+
+    let result = TileableCloudNoise::from_recipe(&Recipe::details(), &desc);
+    assert!(matches!(result, Err(Cancelled)));
+    assert!(desc.control.is_cancelled());
+    assert_eq!(calls.load(Ordering::Relaxed), 3);
+
+    // A control that is already cancelled stops the next bake before its first slice
+    let result = TileableCloudNoise::from_recipe(&Recipe::details(), &desc);
+    assert!(matches!(result, Err(Cancelled)));
+    assert_eq!(calls.load(Ordering::Relaxed), 3);
+}
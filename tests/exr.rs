@@ -0,0 +1,35 @@
+#![cfg(feature = "exr")]
+
+mod common;
+
+use std::{env, fs};
+
+use common::assert_same;
+use tileable_volume_noise::{GenerationDesc, TexelFormat, Tileable3dNoise, TileableCloudNoise};
+
+#[test]
+fn exr_round_trip() {
+    for format in [TexelFormat::Unorm8, TexelFormat::Float32] {
+        let noise = TileableCloudNoise::from_fn(
+            &GenerationDesc {
+                format,
+                ..GenerationDesc::new(5)
+            },
+            // Not in alphabetical order, which EXR sorts the channels in
+            ["worley", "perlin"],
+            |uvw| {
+                [
+                    Tileable3dNoise::worley_noise(uvw, 3.0),
+                    Tileable3dNoise::perlin_noise(uvw, 2.0, 2),
+                ]
+            },
+        )
+        .unwrap();
+        let path = env::temp_dir().join("tileable_volume_noise_round_trip.exr");
+        noise.write_exr(&path).unwrap();
+        let read = TileableCloudNoise::read_exr(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+
+        assert_same(&read, &noise);
+    }
+}
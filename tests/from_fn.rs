@@ -0,0 +1,36 @@
+use tileable_volume_noise::{GenerationDesc, Region, TexelFormat, TileableCloudNoise};
+
+fn desc() -> GenerationDesc {
+    GenerationDesc {
+        format: TexelFormat::Float32,
+        ..GenerationDesc::new(4)
+    }
+}
+
+#[test]
+fn texel_coordinates_follow_sample_axes() {
+    let noise =
+        TileableCloudNoise::from_fn(&desc(), ["x", "y", "z"], |uvw| [uvw.x, uvw.y, uvw.z]).unwrap();
+    for (x, y, z) in [(3, 0, 0), (0, 2, 0), (0, 0, 1), (1, 2, 3)] {
+        let expected = [x, y, z].map(|i| i as f32 / 4.0);
+        let sampled = [0, 1, 2].map(|channel| noise.sample(x, y, z, channel));
+        assert_eq!(sampled, expected, "texel {x} {y} {z}");
+    }
+
+    // Regions and streams see the same coordinates
+    let region =
+        TileableCloudNoise::region_from_fn(&desc(), |uvw| [uvw.x, uvw.y, uvw.z], &Region::full(4))
+            .unwrap();
+    assert!(region == noise.data);
+    let mut streamed = Vec::new();
+    TileableCloudNoise::stream_fn(
+        &desc(),
+        |uvw| [uvw.x, uvw.y, uvw.z],
+        |_, slice| {
+            streamed.extend_from_slice(slice);
+            Ok::<_, tileable_volume_noise::Cancelled>(())
+        },
+    )
+    .unwrap();
+    assert!(streamed == noise.data);
+}
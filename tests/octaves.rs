@@ -0,0 +1,42 @@
+use tileable_volume_noise::{
+    BandLimit, DetailChannels, GenerationDesc, NoiseNode, Recipe, RecipeChannel, TileableCloudNoise,
+};
+
+#[test]
+fn many_octaves_do_not_overflow() {
+    let details = Recipe::details_with(&DetailChannels {
+        fbm_count: 40,
+        ..DetailChannels::default()
+    });
+    assert!(details
+        .channels
+        .iter()
+        .any(|channel| channel.name == "worley_fbm_39"));
+
+    let recipe = Recipe {
+        channels: vec![
+            RecipeChannel {
+                name: "worley_fbm".to_string(),
+                node: NoiseNode::WorleyFbm {
+                    cell_count: 2.0,
+                    weights: vec![1.0 / 40.0; 40],
+                },
+            },
+            RecipeChannel {
+                name: "perlin".to_string(),
+                node: NoiseNode::Perlin {
+                    frequency: 2.0,
+                    octave_count: 40,
+                },
+            },
+        ],
+    };
+    for band_limit in [BandLimit::Off, BandLimit::Drop, BandLimit::Fade] {
+        let desc = GenerationDesc {
+            band_limit,
+            ..GenerationDesc::new(4)
+        };
+        let noise = TileableCloudNoise::from_recipe(&recipe, &desc).unwrap();
+        assert_eq!(noise.data.len(), 4 * 4 * 4 * 2);
+    }
+}
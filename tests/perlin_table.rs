@@ -0,0 +1,34 @@
+mod common;
+
+use common::points;
+use glam::Vec3;
+use tileable_volume_noise::PerlinTable;
+
+#[test]
+fn perlin_table_tiles() {
+    for seed in [0, 1, 42] {
+        let table = PerlinTable::new(seed);
+        for (frequency, octave_count) in [(1.0, 1), (3.0, 2), (4.0, 4), (7.0, 3)] {
+            for p in points() {
+                let noise = table.perlin_noise(p, frequency, octave_count);
+                assert!((0.0..=1.0).contains(&noise), "{noise} at {p}");
+
+                for offset in [Vec3::X, Vec3::Y, Vec3::Z, Vec3::ONE, -Vec3::ONE] {
+                    let tiled = table.perlin_noise(p + offset, frequency, octave_count);
+                    assert!(
+                        (noise - tiled).abs() <= 1e-5,
+                        "seed {seed} frequency {frequency}: {noise} != {tiled} at {p} + {offset}"
+                    );
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn perlin_table_seeds_differ() {
+    let [a, b] = [0, 1].map(PerlinTable::new);
+    assert!(points()
+        .into_iter()
+        .any(|p| a.perlin_noise(p, 4.0, 1) != b.perlin_noise(p, 4.0, 1)));
+}
@@ -0,0 +1,49 @@
+#![cfg(feature = "images")]
+
+mod common;
+
+use std::{env, fs};
+
+use common::assert_same;
+use tileable_volume_noise::{GenerationDesc, SliceLayout, Tileable3dNoise, TileableCloudNoise};
+
+fn noise<const N: usize>(channel_names: [&str; N]) -> TileableCloudNoise {
+    TileableCloudNoise::from_fn(&GenerationDesc::new(5), channel_names, |uvw| {
+        std::array::from_fn(|channel| Tileable3dNoise::worley_noise(uvw, 2.0 + channel as f32))
+    })
+    .unwrap()
+}
+
+#[test]
+fn png_strip_round_trip() {
+    let noise = noise(["r", "g", "b", "a"]);
+    // Same layout as the strips written by the built-in textures
+    let path = env::temp_dir().join("tileable_volume_noise_strip.png");
+    image::save_buffer(
+        &path,
+        &noise.data,
+        noise.resolution * noise.resolution,
+        noise.resolution,
+        image::ColorType::Rgba8,
+    )
+    .unwrap();
+    let read = TileableCloudNoise::read_png_strip(&path).unwrap();
+    fs::remove_file(&path).unwrap();
+
+    assert_same(&read, &noise);
+}
+
+#[test]
+fn channel_pngs_round_trip() {
+    let noise = noise(["density", "erosion"]);
+    let dir = env::temp_dir().join("tileable_volume_noise_channel_pngs");
+    fs::create_dir_all(&dir).unwrap();
+    for layout in [SliceLayout::Strip, SliceLayout::Atlas] {
+        noise.write_channel_pngs(&dir, "volume", layout).unwrap();
+        let read =
+            TileableCloudNoise::read_channel_pngs(&dir, "volume", &["density", "erosion"], layout)
+                .unwrap();
+        assert_same(&read, &noise);
+    }
+    fs::remove_dir_all(&dir).unwrap();
+}
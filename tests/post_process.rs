@@ -0,0 +1,119 @@
+use tileable_volume_noise::{
+    GenerationDesc, ScaleBias, TexelFormat, Tileable3dNoise, TileableCloudNoise,
+};
+
+const CONSTANT: f32 = 0.3;
+
+// Worley noise squeezed in `0.2..=0.7`, and a constant channel
+fn volume() -> TileableCloudNoise {
+    let desc = GenerationDesc {
+        format: TexelFormat::Float32,
+        ..GenerationDesc::new(16)
+    };
+    TileableCloudNoise::from_fn(&desc, ["worley", "constant"], |uvw| {
+        [
+            0.2 + 0.5 * Tileable3dNoise::worley_noise(uvw, 3.0),
+            CONSTANT,
+        ]
+    })
+    .unwrap()
+}
+
+fn values(noise: &TileableCloudNoise, channel: u32) -> Vec<f32> {
+    let res = noise.resolution;
+    (0..res.pow(3))
+        .map(|i| noise.sample(i % res, (i / res) % res, i / (res * res), channel))
+        .collect()
+}
+
+fn assert_constant_unchanged(noise: &TileableCloudNoise) {
+    assert!(values(noise, 1).iter().all(|&value| value == CONSTANT));
+}
+
+#[test]
+fn normalize_is_undone_by_scale_bias() {
+    let original = volume();
+    let mut noise = volume();
+    let scale_biases = noise.normalize();
+
+    let [worley, normalized] = [&original, &noise].map(|noise| values(noise, 0));
+    let ScaleBias { scale, bias } = scale_biases[0];
+    for (&original, &value) in worley.iter().zip(&normalized) {
+        assert!((0.0..=1.0).contains(&value));
+        assert!((value * scale + bias - original).abs() <= 1e-6);
+    }
+    assert_eq!(normalized.iter().copied().fold(f32::MAX, f32::min), 0.0);
+    assert_eq!(normalized.iter().copied().fold(f32::MIN, f32::max), 1.0);
+
+    assert_eq!(scale_biases[1], ScaleBias::IDENTITY);
+    assert_constant_unchanged(&noise);
+}
+
+#[test]
+fn stretch_clamps_outside_percentiles() {
+    let original = volume();
+    let mut noise = volume();
+    let scale_biases = noise.stretch(0.1, 0.9);
+
+    let [worley, stretched] = [&original, &noise].map(|noise| values(noise, 0));
+    let ScaleBias { scale, bias } = scale_biases[0];
+    let (low, high) = (bias, bias + scale);
+    let mut clamped = 0;
+    for (&original, &value) in worley.iter().zip(&stretched) {
+        if original <= low {
+            assert_eq!(value, 0.0);
+            clamped += 1;
+        } else if original >= high {
+            assert_eq!(value, 1.0);
+            clamped += 1;
+        } else {
+            assert!((value * scale + bias - original).abs() <= 1e-6);
+        }
+    }
+    // About 10% of the texels on either side
+    let fraction = clamped as f32 / worley.len() as f32;
+    assert!((0.15..=0.25).contains(&fraction), "{fraction}");
+
+    assert_eq!(scale_biases[1], ScaleBias::IDENTITY);
+    assert_constant_unchanged(&noise);
+}
+
+#[test]
+fn equalize_is_uniform_and_undone_by_curves() {
+    let original = volume();
+    let mut noise = volume();
+    let curves = noise.equalize();
+
+    let [worley, equalized] = [&original, &noise].map(|noise| values(noise, 0));
+    let mean = equalized.iter().sum::<f32>() / equalized.len() as f32;
+    let variance = equalized
+        .iter()
+        .map(|value| (value - mean).powi(2))
+        .sum::<f32>()
+        / equalized.len() as f32;
+    assert!((mean - 0.5).abs() <= 0.01, "mean {mean}");
+    assert!(
+        (variance - 1.0 / 12.0).abs() <= 0.005,
+        "variance {variance}"
+    );
+
+    // The curves hold 256 steps, so they recover the original values up to the spread of the
+    // texels falling in one step, which is wide only in the sparse tails
+    let curve = &curves[0];
+    assert!(curve.windows(2).all(|pair| pair[0] <= pair[1]));
+    let mut error = 0.0;
+    for (&original, &value) in worley.iter().zip(&equalized) {
+        let step = (value * 255.0).round() as usize;
+        let (below, above) = (curve[step.saturating_sub(1)], curve[(step + 1).min(255)]);
+        assert!(
+            (below..=above).contains(&original),
+            "{original} outside {below}..={above}"
+        );
+        error += (curve[step] - original).abs();
+    }
+    let mean_error = error / worley.len() as f32;
+    assert!(mean_error <= 0.002, "mean error {mean_error}");
+
+    assert!(curves[1].iter().all(|&value| value == CONSTANT));
+    assert_constant_unchanged(&noise);
+}
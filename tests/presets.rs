@@ -0,0 +1,124 @@
+use tileable_volume_noise::{CloudPreset, GenerationDesc, NoiseNode, Recipe, TileableCloudNoise};
+
+const PRESETS: [CloudPreset; 5] = [
+    CloudPreset::SebH,
+    CloudPreset::Schneider2015,
+    CloudPreset::Nubis2017,
+    CloudPreset::Frostbite2016,
+    CloudPreset::Mobile,
+];
+
+fn names(recipe: &Recipe) -> Vec<&str> {
+    recipe
+        .channels
+        .iter()
+        .map(|channel| channel.name.as_str())
+        .collect()
+}
+
+// Collects the Perlin frequencies and Worley cell counts of every octave under `node`
+fn frequencies(node: &NoiseNode, frequencies: &mut Vec<f32>) {
+    match node {
+        NoiseNode::Constant(_) => {}
+        NoiseNode::Perlin {
+            frequency,
+            octave_count,
+        } => frequencies
+            .extend((0..*octave_count).map(|octave| frequency * 2f32.powi(octave as i32))),
+        NoiseNode::Worley { cell_count } => frequencies.push(*cell_count),
+        NoiseNode::WorleyFbm {
+            cell_count,
+            weights,
+        } => frequencies
+            .extend((0..weights.len()).map(|octave| cell_count * 2f32.powi(octave as i32))),
+        NoiseNode::Invert(input) => self::frequencies(input, frequencies),
+        NoiseNode::Remap {
+            input,
+            from_min,
+            from_max,
+            to_min,
+            to_max,
+        } => {
+            for node in [input, from_min, from_max, to_min, to_max] {
+                self::frequencies(node, frequencies);
+            }
+        }
+        NoiseNode::WeightedSum(nodes) => {
+            for node in nodes {
+                self::frequencies(&node.node, frequencies);
+            }
+        }
+    }
+}
+
+#[test]
+fn presets_bake_their_channels() {
+    for preset in PRESETS {
+        for (recipe, desc) in [
+            (preset.shape_recipe(), preset.shape_desc()),
+            (preset.detail_recipe(), preset.detail_desc()),
+        ] {
+            let desc = GenerationDesc {
+                resolution: 8,
+                ..desc
+            };
+            let noise = TileableCloudNoise::from_recipe(&recipe, &desc).unwrap();
+            assert_eq!(noise.channel_names, names(&recipe), "{preset:?}");
+            assert_eq!(noise.num_channels as usize, recipe.channels.len());
+            assert_eq!(noise.data.len(), 8 * 8 * 8 * recipe.channels.len());
+        }
+    }
+}
+
+#[test]
+fn nubis_combines_channels() {
+    let preset = CloudPreset::Nubis2017;
+    assert_eq!(names(&preset.shape_recipe()), ["low_frequency_noise"]);
+    assert_eq!(names(&preset.detail_recipe()), ["high_frequency_noise"]);
+    assert_eq!(preset.shape_desc().resolution, 128);
+    assert_eq!(preset.detail_desc().resolution, 32);
+}
+
+#[test]
+fn mobile_halves_resolutions_and_frequencies() {
+    let preset = CloudPreset::Mobile;
+    assert_eq!(preset.shape_desc().resolution, 64);
+    assert_eq!(preset.detail_desc().resolution, 16);
+    assert_eq!(
+        names(&preset.shape_recipe()),
+        names(&CloudPreset::Frostbite2016.shape_recipe())
+    );
+    assert_eq!(
+        names(&preset.detail_recipe()),
+        names(&CloudPreset::Frostbite2016.detail_recipe())
+    );
+
+    for (recipe, full) in [
+        (
+            preset.shape_recipe(),
+            CloudPreset::Frostbite2016.shape_recipe(),
+        ),
+        (
+            preset.detail_recipe(),
+            CloudPreset::Frostbite2016.detail_recipe(),
+        ),
+    ] {
+        let mut halved = Vec::new();
+        let mut expected = Vec::new();
+        for (channel, full) in recipe.channels.iter().zip(&full.channels) {
+            frequencies(&channel.node, &mut halved);
+            frequencies(&full.node, &mut expected);
+        }
+        assert_eq!(
+            halved,
+            expected
+                .iter()
+                .map(|frequency| frequency * 0.5)
+                .collect::<Vec<_>>()
+        );
+        // Noise only tiles with a whole number of cells or periods
+        for frequency in halved {
+            assert!(frequency >= 1.0 && frequency.fract() == 0.0, "{frequency}");
+        }
+    }
+}
@@ -0,0 +1,103 @@
+use tileable_volume_noise::{GenerationDesc, Quantization, TileableCloudNoise};
+
+// Values whose fractional parts in 8-bit steps spread evenly over the volume
+fn ramp(uvw: glam::Vec3) -> f32 {
+    ((uvw.x * 7.3 + uvw.y * 3.1 + uvw.z * 1.7) * 0.37).fract()
+}
+
+fn bake(
+    resolution: u32,
+    quantization: Quantization,
+    texel: impl Fn(glam::Vec3) -> f32 + Sync,
+) -> TileableCloudNoise {
+    let desc = GenerationDesc {
+        quantization,
+        ..GenerationDesc::new(resolution)
+    };
+    TileableCloudNoise::from_fn(&desc, ["value"], |uvw| [texel(uvw)]).unwrap()
+}
+
+// Errors of the 8-bit texels relative to the values they store, in steps
+fn errors(noise: &TileableCloudNoise, texel: impl Fn(glam::Vec3) -> f32) -> Vec<f32> {
+    let res = noise.resolution;
+    (0..res.pow(3))
+        .map(|i| {
+            let [x, y, z] = [i % res, (i / res) % res, i / (res * res)];
+            let uvw = glam::Vec3::new(x as f32, y as f32, z as f32) / res as f32;
+            (noise.sample(x, y, z, 0) - texel(uvw)) * 255.0
+        })
+        .collect()
+}
+
+fn mean(values: &[f32]) -> f32 {
+    values.iter().sum::<f32>() / values.len() as f32
+}
+
+#[test]
+fn round_removes_truncation_bias() {
+    let truncated = errors(&bake(16, Quantization::Truncate, ramp), ramp);
+    assert!(
+        (mean(&truncated) + 0.5).abs() <= 0.05,
+        "{}",
+        mean(&truncated)
+    );
+    assert!(truncated.iter().all(|error| (-1.0..=0.0).contains(error)));
+
+    let rounded = errors(&bake(16, Quantization::Round, ramp), ramp);
+    assert!(mean(&rounded).abs() <= 0.05, "{}", mean(&rounded));
+    assert!(rounded.iter().all(|error| error.abs() <= 0.5 + 1e-3));
+}
+
+#[test]
+fn dither_patterns_tile() {
+    // A constant between two steps, so that the dithered texels show the pattern. Its fraction
+    // is not a multiple of 1/8, which the coarser levels of the Bayer matrix would round alike.
+    let value = (128.0 + 0.3) / 255.0;
+    for resolution in [6u32, 12, 20, 24, 32] {
+        for (quantization, period) in [
+            (
+                Quantization::OrderedDither,
+                1 << resolution.trailing_zeros().min(2),
+            ),
+            (Quantization::BlueNoiseDither, gcd(resolution, 16)),
+        ] {
+            let noise = bake(resolution, quantization, |_| value);
+            let sample = |x: u32, y: u32, z: u32| {
+                noise.sample(x % resolution, y % resolution, z % resolution, 0)
+            };
+            let repeats = |period: u32| {
+                (0..resolution.pow(3)).all(|i| {
+                    let [x, y, z] = [
+                        i % resolution,
+                        (i / resolution) % resolution,
+                        i / (resolution * resolution),
+                    ];
+                    let texel = sample(x, y, z);
+                    texel == sample(x + period, y, z)
+                        && texel == sample(x, y + period, z)
+                        && texel == sample(x, y, z + period)
+                })
+            };
+            assert!(repeats(period), "{quantization:?} at {resolution}");
+            if period > 1 {
+                assert!(!repeats(period / 2), "{quantization:?} at {resolution}");
+            }
+
+            // Dithering keeps the mean of the volume
+            let error = errors(&noise, |_| value);
+            assert!(
+                mean(&error).abs() <= 0.5 / (period.pow(3) as f32),
+                "{quantization:?} at {resolution}: {}",
+                mean(&error)
+            );
+        }
+    }
+}
+
+fn gcd(a: u32, b: u32) -> u32 {
+    if b == 0 {
+        a
+    } else {
+        gcd(b, a % b)
+    }
+}
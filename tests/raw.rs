@@ -0,0 +1,53 @@
+#![cfg(feature = "serde")]
+
+mod common;
+
+use std::{env, fs, io};
+
+use common::assert_same;
+use tileable_volume_noise::{GenerationDesc, TexelFormat, Tileable3dNoise, TileableCloudNoise};
+
+#[test]
+fn raw_round_trip() {
+    let noise = TileableCloudNoise::from_fn(
+        &GenerationDesc {
+            format: TexelFormat::Unorm16,
+            ..GenerationDesc::new(6)
+        },
+        ["perlin", "worley"],
+        |uvw| {
+            [
+                Tileable3dNoise::perlin_noise(uvw, 2.0, 2),
+                Tileable3dNoise::worley_noise(uvw, 3.0),
+            ]
+        },
+    )
+    .unwrap();
+    let path = env::temp_dir().join("tileable_volume_noise_round_trip.raw");
+    noise.write_raw(&path).unwrap();
+    let read = TileableCloudNoise::read_raw(&path);
+    fs::remove_file(&path).unwrap();
+    fs::remove_file(path.with_extension("json")).unwrap();
+
+    assert_same(&read.unwrap(), &noise);
+}
+
+#[test]
+fn raw_rejects_overflowing_sidecars() {
+    let noise = TileableCloudNoise::from_fn(&GenerationDesc::new(2), ["x"], |uvw| [uvw.x]).unwrap();
+    let path = env::temp_dir().join("tileable_volume_noise_overflow.raw");
+    noise.write_raw(&path).unwrap();
+    // 2^22 cubed overflows 64 bits
+    let sidecar = fs::read_to_string(path.with_extension("json"))
+        .unwrap()
+        .replace("\"resolution\": 2", "\"resolution\": 4194304");
+    fs::write(path.with_extension("json"), sidecar).unwrap();
+    let read = TileableCloudNoise::read_raw(&path);
+    fs::remove_file(&path).unwrap();
+    fs::remove_file(path.with_extension("json")).unwrap();
+
+    assert_eq!(
+        read.err().map(|error| error.kind()),
+        Some(io::ErrorKind::InvalidData)
+    );
+}
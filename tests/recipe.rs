@@ -0,0 +1,127 @@
+use tileable_volume_noise::{
+    DetailAlpha, DetailChannels, DetailOctaves, GenerationDesc, NoiseNode, PerlinWorleyMode,
+    Recipe, RecipeChannel, TexelFormat, TileableCloudNoise,
+};
+
+fn bake(channels: Vec<RecipeChannel>) -> TileableCloudNoise {
+    let desc = GenerationDesc {
+        format: TexelFormat::Float32,
+        ..GenerationDesc::new(16)
+    };
+    TileableCloudNoise::from_recipe(&Recipe { channels }, &desc).unwrap()
+}
+
+fn perlin_worley(mode: PerlinWorleyMode) -> RecipeChannel {
+    Recipe::cloud_shape_and_erosion_with(mode)
+        .channels
+        .remove(0)
+}
+
+// Values of every texel of `channel`
+fn values(noise: &TileableCloudNoise, channel: u32) -> Vec<f32> {
+    let resolution = noise.resolution;
+    (0..resolution)
+        .flat_map(|z| (0..resolution).flat_map(move |y| (0..resolution).map(move |x| (x, y, z))))
+        .map(|(x, y, z)| noise.sample(x, y, z, channel))
+        .collect()
+}
+
+#[test]
+fn figure_4_7_multiplies_worley_by_perlin() {
+    // The text description remaps Perlin noise from the Worley FBM to 1
+    let NoiseNode::Remap { input, to_min, .. } =
+        perlin_worley(PerlinWorleyMode::TextDescription).node
+    else {
+        panic!("the text description is not a remap");
+    };
+    let noise = bake(vec![
+        perlin_worley(PerlinWorleyMode::Figure4_7),
+        RecipeChannel {
+            name: "perlin".to_string(),
+            node: *input,
+        },
+        RecipeChannel {
+            name: "worley_fbm".to_string(),
+            node: *to_min,
+        },
+    ]);
+
+    let figure = values(&noise, 0);
+    let perlin = values(&noise, 1);
+    let worley_fbm = values(&noise, 2);
+    for ((figure, perlin), worley_fbm) in figure.into_iter().zip(perlin).zip(worley_fbm) {
+        assert!(
+            (figure - worley_fbm * perlin).abs() <= 1e-6,
+            "{figure} != {worley_fbm} * {perlin}"
+        );
+    }
+}
+
+#[test]
+fn schneider_dilation_is_normalized() {
+    let noise = bake(vec![perlin_worley(PerlinWorleyMode::SchneiderDilated)]);
+    for value in values(&noise, 0) {
+        assert!((0.0..=1.0).contains(&value), "{value}");
+    }
+}
+
+#[test]
+fn non_power_of_two_octaves_overlap() {
+    let recipe = Recipe::details_with(&DetailChannels {
+        octaves: DetailOctaves::NonPowerOfTwo,
+        ..DetailChannels::default()
+    });
+    for (index, channel) in recipe.channels.iter().take(3).enumerate() {
+        let NoiseNode::WeightedSum(octaves) = &channel.node else {
+            panic!("{}: {:?}", channel.name, channel.node);
+        };
+        let octaves = octaves
+            .iter()
+            .map(|octave| match &octave.node {
+                NoiseNode::Invert(node) => match **node {
+                    NoiseNode::Worley { cell_count } => (octave.weight, cell_count),
+                    ref node => panic!("{}: {node:?}", channel.name),
+                },
+                node => panic!("{}: {node:?}", channel.name),
+            })
+            .collect::<Vec<_>>();
+        let cell_count = (4 + 3 * index) as f32;
+        assert_eq!(octaves, [(0.75, cell_count), (0.25, cell_count + 3.0)]);
+    }
+}
+
+#[test]
+fn detail_alpha_selects_last_channel() {
+    let names = |alpha| {
+        Recipe::details_with(&DetailChannels {
+            alpha,
+            ..DetailChannels::default()
+        })
+        .channels
+        .into_iter()
+        .map(|channel| channel.name)
+        .collect::<Vec<_>>()
+    };
+
+    assert_eq!(
+        names(DetailAlpha::Unused),
+        ["worley_fbm_0", "worley_fbm_1", "worley_fbm_2", "unused"]
+    );
+    assert_eq!(
+        names(DetailAlpha::Omitted),
+        ["worley_fbm_0", "worley_fbm_1", "worley_fbm_2"]
+    );
+    assert_eq!(
+        names(DetailAlpha::NextFbm),
+        [
+            "worley_fbm_0",
+            "worley_fbm_1",
+            "worley_fbm_2",
+            "worley_fbm_3"
+        ]
+    );
+    assert_eq!(
+        names(DetailAlpha::Node(NoiseNode::Constant(0.5))),
+        ["worley_fbm_0", "worley_fbm_1", "worley_fbm_2", "alpha"]
+    );
+}
@@ -0,0 +1,37 @@
+use tileable_volume_noise::{GenerationDesc, TileableCloudNoise};
+
+// 64-bit FNV-1a, stable across Rust versions unlike `DefaultHasher`
+fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf2_9ce4_8422_2325, |hash, &byte| {
+        (hash ^ byte as u64).wrapping_mul(0x0000_0100_0000_01b3)
+    })
+}
+
+// Hashes of the textures generated by the original implementation, which every optimization of
+// the generators must reproduce byte for byte
+const DETAILS_HASH: u64 = 0xc6bc_7fee_a92b_c60b;
+const SHAPE_32_HASH: u64 = 0x6992_1cce_77c2_4866;
+
+#[test]
+fn details_match_reference() {
+    // Same bake as `TileableCloudNoise::details_texture`, without writing the preview images
+    let details = TileableCloudNoise::details_texture_with(&GenerationDesc::new(32)).unwrap();
+    assert_eq!(
+        fnv1a(&details.data),
+        DETAILS_HASH,
+        "{:#x}",
+        fnv1a(&details.data)
+    );
+}
+
+#[test]
+fn reduced_shape_matches_reference() {
+    let shape =
+        TileableCloudNoise::cloud_shape_and_erosion_texture_with(&GenerationDesc::new(32)).unwrap();
+    assert_eq!(
+        fnv1a(&shape.data),
+        SHAPE_32_HASH,
+        "{:#x}",
+        fnv1a(&shape.data)
+    );
+}
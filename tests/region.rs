@@ -0,0 +1,57 @@
+use tileable_volume_noise::{
+    GenerationDesc, Quantization, Recipe, ReconstructionFilter, Region, SamplePattern,
+    Supersampling, TileableCloudNoise,
+};
+
+#[test]
+fn regions_match_full_volume() {
+    let res = 12;
+    let desc = GenerationDesc {
+        seed: 3,
+        quantization: Quantization::BlueNoiseDither,
+        supersampling: Supersampling {
+            samples_per_axis: 2,
+            pattern: SamplePattern::Jittered,
+            filter: ReconstructionFilter::Gaussian,
+        },
+        ..GenerationDesc::new(res as u32)
+    };
+    let recipe = Recipe::cloud_shape_and_erosion();
+    let full = TileableCloudNoise::from_recipe(&recipe, &desc).unwrap();
+    let texel_len = (full.num_channels * full.bytes_per_channel) as usize;
+
+    for region in [
+        Region::full(res as u32),
+        Region {
+            x: 3..10,
+            y: 0..5,
+            z: 9..12,
+        },
+        // Wrapping below 0 and beyond the resolution
+        Region {
+            x: -5..7,
+            y: 10..18,
+            z: -1..2,
+        },
+        // Covering the volume more than once
+        Region {
+            x: -13..14,
+            y: 4..5,
+            z: 23..25,
+        },
+    ] {
+        let data = TileableCloudNoise::region_from_recipe(&recipe, &desc, &region).unwrap();
+
+        let mut expected = Vec::new();
+        for z in region.z.clone() {
+            for y in region.y.clone() {
+                for x in region.x.clone() {
+                    let [x, y, z] = [x, y, z].map(|i| i.rem_euclid(res) as usize);
+                    let texel = (z * res as usize + y) * res as usize + x;
+                    expected.extend_from_slice(&full.data[texel * texel_len..][..texel_len]);
+                }
+            }
+        }
+        assert!(data == expected, "{region:?}");
+    }
+}
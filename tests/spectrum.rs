@@ -0,0 +1,38 @@
+#![cfg(feature = "spectrum")]
+
+use std::f32::consts::TAU;
+
+use tileable_volume_noise::{GenerationDesc, TexelFormat, Tileable3dNoise, TileableCloudNoise};
+
+const RESOLUTION: u32 = 16;
+
+fn volume(texel: impl Fn(glam::Vec3) -> f32 + Sync) -> TileableCloudNoise {
+    let desc = GenerationDesc {
+        format: TexelFormat::Float32,
+        ..GenerationDesc::new(RESOLUTION)
+    };
+    TileableCloudNoise::from_fn(&desc, ["value"], |uvw| [texel(uvw)]).unwrap()
+}
+
+#[test]
+fn worley_aliases_at_texel_frequency() {
+    let smooth = volume(|uvw| Tileable3dNoise::worley_noise(uvw, 4.0)).power_spectrum(0);
+    assert!(!smooth.is_aliased(), "{}", smooth.energy_above_nyquist);
+
+    let aliased =
+        volume(|uvw| Tileable3dNoise::worley_noise(uvw, RESOLUTION as f32)).power_spectrum(0);
+    assert!(aliased.is_aliased(), "{}", aliased.energy_above_nyquist);
+}
+
+#[test]
+fn sine_power_is_at_its_frequency() {
+    // Along each axis in turn, so that every stride of the transform is exercised
+    for axis in 0..3 {
+        let spectrum = volume(|uvw| 0.5 + 0.5 * (TAU * 3.0 * uvw[axis]).sin()).power_spectrum(0);
+        let peak = (0..spectrum.power.len())
+            .max_by(|&a, &b| spectrum.power[a].total_cmp(&spectrum.power[b]))
+            .unwrap();
+        assert_eq!(peak, 3, "axis {axis}: {:?}", spectrum.power);
+        assert!(spectrum.energy_above_nyquist <= 1e-6);
+    }
+}
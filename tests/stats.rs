@@ -0,0 +1,45 @@
+use tileable_volume_noise::{GenerationDesc, Quantization, TileableCloudNoise};
+
+#[test]
+fn histogram_counts_stored_values() {
+    let desc = GenerationDesc {
+        quantization: Quantization::Round,
+        ..GenerationDesc::new(8)
+    };
+    // Every 8-bit value twice, and a ramp pushed out of range at both ends
+    let noise = TileableCloudNoise::from_fn(&desc, ["bytes", "clamped"], |uvw| {
+        let index = ((uvw.z * 8.0 + uvw.y) * 8.0 + uvw.x) * 8.0;
+        [(index % 256.0) / 255.0, (uvw.x * 2.0 - 0.5).clamp(0.0, 1.0)]
+    })
+    .unwrap();
+    let stats = noise.stats();
+
+    let bytes = &stats[0];
+    assert!(bytes.histogram.iter().all(|&count| count == 2));
+    assert_eq!(bytes.saturated_count, 4);
+    assert_eq!((bytes.min, bytes.max), (0.0, 1.0));
+    assert!((bytes.mean - 0.5).abs() <= 1e-6);
+    assert!(!bytes.is_saturated());
+
+    let clamped = &stats[1];
+    // Columns 0 to 2 end up at 0, columns 6 and 7 at 1
+    assert_eq!(clamped.saturated_count, 5 * 8 * 8);
+    assert_eq!(clamped.histogram[0], 3 * 8 * 8);
+    assert_eq!(clamped.histogram[255], 2 * 8 * 8);
+    assert!(clamped.is_saturated());
+}
+
+#[test]
+fn constant_channels_are_not_saturated() {
+    let details = TileableCloudNoise::details_texture_with(&GenerationDesc::new(8)).unwrap();
+    let unused = details
+        .channel_names
+        .iter()
+        .position(|name| name == "unused")
+        .unwrap();
+    let stats = &details.stats()[unused];
+
+    assert_eq!(stats.min, stats.max);
+    assert_eq!(stats.saturated_count, 8 * 8 * 8);
+    assert!(!stats.is_saturated());
+}
@@ -0,0 +1,103 @@
+use tileable_volume_noise::{
+    GenerationDesc, NoiseNode, Recipe, RecipeChannel, StreamFormat, TexelFormat, TileableCloudNoise,
+};
+
+fn stream(recipe: &Recipe, desc: &GenerationDesc, format: StreamFormat) -> Vec<u8> {
+    let mut output = Vec::new();
+    TileableCloudNoise::write_recipe_stream(recipe, desc, format, &mut output).unwrap();
+    output
+}
+
+// Splits a NRRD file in its header lines and its payload
+fn split_nrrd(nrrd: &[u8]) -> (Vec<String>, &[u8]) {
+    let end = nrrd
+        .windows(2)
+        .position(|window| window == b"\n\n")
+        .expect("the header ends with a blank line");
+    let header = std::str::from_utf8(&nrrd[..end]).unwrap();
+    (header.lines().map(String::from).collect(), &nrrd[end + 2..])
+}
+
+#[test]
+fn raw_stream_matches_bake() {
+    let recipe = Recipe::details();
+    for format in [TexelFormat::Unorm8, TexelFormat::Float32] {
+        let desc = GenerationDesc {
+            format,
+            ..GenerationDesc::new(12)
+        };
+        let bake = TileableCloudNoise::from_recipe(&recipe, &desc).unwrap();
+        assert!(
+            stream(&recipe, &desc, StreamFormat::Raw) == bake.data,
+            "{format:?}"
+        );
+    }
+}
+
+#[test]
+fn nrrd_stream_describes_payload() {
+    let recipe = Recipe::details();
+    let res = 12;
+    for (format, kind, bytes) in [
+        (TexelFormat::Unorm8, "uint8", 1),
+        (TexelFormat::Unorm16, "uint16", 2),
+        (TexelFormat::Float32, "float", 4),
+    ] {
+        let desc = GenerationDesc {
+            format,
+            ..GenerationDesc::new(res)
+        };
+        let nrrd = stream(&recipe, &desc, StreamFormat::Nrrd);
+        let (header, payload) = split_nrrd(&nrrd);
+
+        let channels = recipe.channels.len();
+        assert_eq!(header[0], "NRRD0004");
+        for line in [
+            format!("type: {kind}"),
+            "dimension: 4".to_string(),
+            format!("sizes: {channels} {res} {res} {res}"),
+            "kinds: list domain domain domain".to_string(),
+            "encoding: raw".to_string(),
+        ] {
+            assert!(header.contains(&line), "{line:?} missing from {header:?}");
+        }
+        // Single bytes have no endianness
+        assert_eq!(
+            header.contains(&"endian: little".to_string()),
+            format != TexelFormat::Unorm8,
+            "{header:?}"
+        );
+        assert_eq!(payload.len(), (res as usize).pow(3) * channels * bytes);
+        assert!(payload == stream(&recipe, &desc, StreamFormat::Raw));
+    }
+}
+
+#[test]
+fn single_channel_nrrd_is_3d() {
+    let recipe = Recipe {
+        channels: vec![RecipeChannel {
+            name: "worley".to_string(),
+            node: NoiseNode::Worley { cell_count: 4.0 },
+        }],
+    };
+    let desc = GenerationDesc {
+        format: TexelFormat::Unorm16,
+        ..GenerationDesc::new(8)
+    };
+    let nrrd = stream(&recipe, &desc, StreamFormat::Nrrd);
+    let (header, payload) = split_nrrd(&nrrd);
+
+    for line in [
+        "dimension: 3",
+        "sizes: 8 8 8",
+        "kinds: domain domain domain",
+        "endian: little",
+        "channel_names:=worley",
+    ] {
+        assert!(
+            header.iter().any(|header_line| header_line == line),
+            "{line:?} missing from {header:?}"
+        );
+    }
+    assert_eq!(payload.len(), 8 * 8 * 8 * 2);
+}
@@ -0,0 +1,56 @@
+use std::{
+    collections::HashSet,
+    sync::{Arc, Mutex},
+};
+
+use rayon::ThreadPoolBuilder;
+use tileable_volume_noise::{
+    GenerationControl, GenerationDesc, Recipe, Threads, TileableCloudNoise,
+};
+
+// Bakes the detail texture in `threads`, returning its data and the names of the threads and
+// sizes of the pools that reported progress
+fn bake(threads: Threads) -> (Vec<u8>, HashSet<(Option<String>, usize)>) {
+    let workers = Arc::new(Mutex::new(HashSet::new()));
+    let control = GenerationControl::new().with_progress({
+        let workers = workers.clone();
+        move |_, _| {
+            let name = std::thread::current().name().map(String::from);
+            workers
+                .lock()
+                .unwrap()
+                .insert((name, rayon::current_num_threads()));
+        }
+    });
+    let desc = GenerationDesc {
+        control,
+        threads,
+        ..GenerationDesc::new(12)
+    };
+    let data = TileableCloudNoise::from_recipe(&Recipe::details(), &desc)
+        .unwrap()
+        .data;
+    let workers = workers.lock().unwrap().clone();
+    (data, workers)
+}
+
+#[test]
+fn bakes_run_in_their_pool() {
+    let (global, _) = bake(Threads::Global);
+
+    let (counted, workers) = bake(Threads::Count(3));
+    assert!(counted == global);
+    assert!(workers.iter().all(|&(_, size)| size == 3), "{workers:?}");
+
+    let pool = ThreadPoolBuilder::new()
+        .num_threads(1)
+        .thread_name(|index| format!("bake-pool-{index}"))
+        .build()
+        .unwrap();
+    let (pooled, workers) = bake(Threads::Pool(Arc::new(pool)));
+    assert!(pooled == global);
+    assert_eq!(
+        workers,
+        HashSet::from([(Some("bake-pool-0".to_string()), 1)])
+    );
+}
@@ -0,0 +1,88 @@
+use std::{env, fs, io};
+
+use tileable_volume_noise::{GenerationDesc, TileableCloudNoise};
+
+// Reads the little-endian values of a VDB file in order
+struct Reader<'a> {
+    bytes: &'a [u8],
+    position: usize,
+}
+
+impl Reader<'_> {
+    fn take(&mut self, len: usize) -> &[u8] {
+        let bytes = &self.bytes[self.position..self.position + len];
+        self.position += len;
+        bytes
+    }
+
+    fn u32(&mut self) -> u32 {
+        u32::from_le_bytes(self.take(4).try_into().unwrap())
+    }
+
+    fn i64(&mut self) -> i64 {
+        i64::from_le_bytes(self.take(8).try_into().unwrap())
+    }
+
+    fn string(&mut self) -> String {
+        let len = self.u32() as usize;
+        String::from_utf8(self.take(len).to_vec()).unwrap()
+    }
+}
+
+#[test]
+fn vdb_structure() {
+    // 12 is not a multiple of the leaf size, 2 leaves along each axis are partially filled
+    let noise =
+        TileableCloudNoise::from_fn(&GenerationDesc::new(12), ["x", "y"], |uvw| [uvw.x, uvw.y])
+            .unwrap();
+    let path = env::temp_dir().join("tileable_volume_noise_structure.vdb");
+    noise.write_vdb(&path, 1, 0.5).unwrap();
+    let bytes = fs::read(&path).unwrap();
+    fs::remove_file(&path).unwrap();
+
+    let mut reader = Reader {
+        bytes: &bytes,
+        position: 0,
+    };
+    assert_eq!(reader.i64(), 0x5644_4220, "magic");
+    assert_eq!(reader.u32(), 224, "file version");
+    reader.take(8); // Library version
+    assert_eq!(reader.take(1), [1], "has grid offsets");
+    reader.take(36); // UUID
+    assert_eq!(reader.u32(), 0, "file metadata");
+    assert_eq!(reader.u32(), 1, "grid count");
+    assert_eq!(reader.string(), "y");
+    assert_eq!(reader.string(), "Tree_float_5_4_3");
+    assert_eq!(reader.string(), "");
+
+    let [grid_pos, block_pos, end_pos] = [reader.i64(), reader.i64(), reader.i64()];
+    assert_eq!(grid_pos, reader.position as i64);
+    assert_eq!(end_pos, bytes.len() as i64);
+
+    // Value mask, then the compression byte and 512 values of every leaf
+    let leaf_len = 64 + 1 + 512 * 4;
+    assert_eq!((end_pos - block_pos) % leaf_len, 0);
+    assert_eq!((end_pos - block_pos) / leaf_len, 2 * 2 * 2, "leaf count");
+
+    // The first leaf starts at the origin, stored along z first
+    let values = block_pos as usize + 65;
+    let value = |i: usize| {
+        f32::from_le_bytes(
+            bytes[values + i * 4..values + i * 4 + 4]
+                .try_into()
+                .unwrap(),
+        )
+    };
+    assert_eq!(value(0), noise.sample(0, 0, 0, 1));
+    assert_eq!(value(1), noise.sample(0, 0, 1, 1));
+    assert_eq!(value(8), noise.sample(0, 1, 0, 1));
+}
+
+#[test]
+fn vdb_rejects_invalid_channels() {
+    let noise = TileableCloudNoise::from_fn(&GenerationDesc::new(4), ["x"], |uvw| [uvw.x]).unwrap();
+    let path = env::temp_dir().join("tileable_volume_noise_invalid.vdb");
+    let error = noise.write_vdb(&path, 1, 1.0).unwrap_err();
+    assert_eq!(error.kind(), io::ErrorKind::InvalidInput);
+    assert!(!path.exists());
+}